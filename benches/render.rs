@@ -0,0 +1,95 @@
+//! Benchmarks for rendering, comparing the exact-size rendering of [`Template::render_with`]
+//! with the previous rendering (collecting all parts into a `Vec<&str>` and joining them),
+//! with streaming into a growing [`String`] (like [`Template::render_to_fmt`] does)
+//! and with rendering a [`BoundTemplate`](new_string_template::template::BoundTemplate) from a slice
+
+use criterion::{
+    criterion_group,
+    criterion_main,
+    BenchmarkId,
+    Criterion,
+    Throughput,
+};
+use new_string_template::template::Template;
+use std::collections::HashMap;
+
+/// Build a template with `placeholders` placeholders, each preceded by `literal_len` bytes of literal text
+///
+/// Returns the Template String, the Template and the values for all placeholders.
+fn build(placeholders: usize, literal_len: usize) -> (String, Template, HashMap<String, String>) {
+    let literal = "x".repeat(literal_len);
+    let mut src = String::new();
+    let mut values = HashMap::new();
+
+    for i in 0..placeholders {
+        src.push_str(&literal);
+        src.push_str(&format!("{{data{}}}", i));
+        values.insert(format!("data{}", i), format!("value number {}", i));
+    }
+
+    return (src.clone(), Template::new(src), values);
+}
+
+/// Render like before exact-size rendering: collect the literal parts and values into a `Vec<&str>` and join them
+///
+/// `spans` are the start, end and name of every placeholder, like the matches stored in a Template.
+fn render_join(src: &str, spans: &[(usize, usize, &str)], values: &HashMap<String, String>) -> Option<String> {
+    let mut parts: Vec<&str> = Vec::with_capacity(spans.len());
+    let mut last_index = 0;
+
+    for (start, end, name) in spans {
+        parts.push(&src[last_index..*start]);
+        parts.push(values.get(*name)?);
+        last_index = *end;
+    }
+
+    if last_index < src.len() {
+        parts.push(&src[last_index..]);
+    }
+
+    return Some(parts.join(""));
+}
+
+fn bench_render(c: &mut Criterion) {
+    let mut group = c.benchmark_group("render");
+
+    for &placeholders in &[1, 10, 100] {
+        for &literal_len in &[8, 256] {
+            let (src, templ, values) = build(placeholders, literal_len);
+            let id = format!("{}x{}", placeholders, literal_len);
+            let len = templ.render_with(&values).expect("Expected Result to be Ok").len();
+            group.throughput(Throughput::Bytes(len as u64));
+
+            let spans: Vec<(usize, usize, &str)> = templ
+                .placeholders()
+                .map(|v| return (v.span().start(), v.span().end(), v.name()))
+                .collect();
+            group.bench_with_input(BenchmarkId::new("join", &id), &values, |b, values| {
+                return b.iter(|| return render_join(&src, &spans, values).expect("Expected all values to exist"));
+            });
+            group.bench_with_input(BenchmarkId::new("exact", &id), &values, |b, values| {
+                return b.iter(|| return templ.render_with(values).expect("Expected Result to be Ok"));
+            });
+            group.bench_with_input(BenchmarkId::new("streaming", &id), &values, |b, values| {
+                return b.iter(|| {
+                    let mut out = String::new();
+                    templ.render_to_fmt(&mut out, values).expect("Expected Result to be Ok");
+
+                    return out;
+                });
+            });
+
+            let keys: Vec<&String> = values.keys().collect();
+            let bound_values: Vec<&String> = keys.iter().map(|key| return &values[*key]).collect();
+            let bound = templ.bind(&keys).expect("Expected Result to be Ok");
+            group.bench_with_input(BenchmarkId::new("bound", &id), &bound_values, |b, values| {
+                return b.iter(|| return bound.render(values).expect("Expected Result to be Ok"));
+            });
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_render);
+criterion_main!(benches);
@@ -0,0 +1,616 @@
+/*!
+Compile-time checked templates for [`new_string_template`](https://docs.rs/new_string_template).
+
+# Usage
+
+Add this to your `Cargo.toml` (both crates are needed, because the macros expand to code using `new_string_template`):
+
+```toml
+[dependencies]
+new_string_template = "1.5"
+new_string_template_macros = "0.1"
+```
+
+Example:
+
+```rust
+use new_string_template_macros::template;
+
+struct Greeting {
+    name: String,
+}
+
+// this fails to compile if the template is invalid or "Greeting" has no field "name"
+let templ = template!("Hello {name}", Greeting);
+let data = vec![("name", "World")];
+
+assert_eq!("Hello World", templ.render_with(&data).expect("Expected Result to be Ok"));
+```
+
+Example with `TemplateData`:
+
+```rust
+use new_string_template::template::Template;
+use new_string_template_macros::TemplateData;
+
+#[derive(TemplateData)]
+struct Greeting {
+    name: String,
+    #[template(rename = "times")]
+    count: u32,
+}
+
+let templ = Template::new("Hello {name} x{times}");
+let data = Greeting { name: "World".into(), count: 2 };
+
+assert_eq!("Hello World x2", templ.render_with(&data).expect("Expected Result to be Ok"));
+```
+*/
+
+use new_string_template::template::Template;
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse::{
+        Parse,
+        ParseStream,
+    },
+    parse_macro_input,
+    Data,
+    DeriveInput,
+    Generics,
+    Ident,
+    Index,
+    LitStr,
+    Member,
+    Path,
+    Token,
+    Type,
+};
+
+/// Create a [`Template`] from a string literal, which is parsed at compile time with the default syntax.
+///
+/// A invalid template (like a `{#if}` without `{/if}`) is a compile error instead of a runtime error.
+/// Optionally a struct can be given as second argument, then every placeholder and every `{#if}` condition and `{#each}` list
+/// (outside of `{#each}` sections) has to be a field of that struct, for paths like `{user.name}` only the first segment is checked.
+/// Placeholders with a default value (like `{name:-anon}`) do not need a field.
+/// For a generic struct, either give concrete arguments (like `Data<String>`) or declare the generic parameters before it (like `<T> Data<T>`).
+///
+/// The macro expands to a expression of type [`Template`], which still parses the Template String at runtime every time it is evaluated
+/// (`Template` cannot be created in a `const`), but building it cannot fail anymore.
+/// Store the Template (for example in a `lazy_static` or `OnceLock`) if the expression is evaluated often.
+/// # Example
+/// ```rust
+/// # use new_string_template_macros::template;
+/// struct Job {
+///     name:   String,
+///     status: String,
+/// }
+///
+/// let templ = template!("Job {name}: {status | upper}", Job);
+/// let data = vec![("name", "build"), ("status", "ok")];
+///
+/// assert_eq!("Job build: OK", templ.render_with(&data).expect("Expected Result to be Ok"));
+///
+/// struct Wrapper<T> {
+///     value: T,
+/// }
+///
+/// let templ = template!("{value} ({unit:-none})", <T> Wrapper<T>);
+/// assert_eq!("10 (none)", templ.render_nofail_with(&vec![("value", "10")]));
+/// ```
+///
+/// A typo in a placeholder fails to compile:
+/// ```rust,compile_fail
+/// # use new_string_template_macros::template;
+/// struct Job {
+///     name: String,
+/// }
+///
+/// let templ = template!("Job {nmae}", Job);
+/// ```
+///
+/// And so does a typo in a section:
+/// ```rust,compile_fail
+/// # use new_string_template_macros::template;
+/// struct Job {
+///     name:    String,
+///     visible: bool,
+/// }
+///
+/// let templ = template!("{#if visble}Job {name}{/if}", Job);
+/// ```
+#[proc_macro]
+pub fn template(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as TemplateInput);
+
+    return expand_template(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into();
+}
+
+/// Derive [`ValueSource`](https://docs.rs/new_string_template/latest/new_string_template/source/trait.ValueSource.html) for a struct,
+/// so it can be used directly to render a Template.
+///
+/// Every field provides its value under its name, fields of tuple structs under their index (like `{0}`).
+/// So every field that is not skipped or flattened has to implement [`Display`](std::fmt::Display),
+/// `String` and `&str` fields are borrowed, all other fields are converted with [`ToString`] on every lookup.  
+/// Fields with the type `bool` also provide a typed boolean for `{#if}` conditions, fields with a integer or float type a typed number for numeric format specs like `{age:03}`.
+/// The following field attributes are supported:
+/// - `#[template(rename = "other")]`: provide the value under `other` instead of the field name
+/// - `#[template(skip)]`: do not provide the field
+/// - `#[template(flatten)]`: provide all values of the field (which has to be a `ValueSource` itself) as if they were fields of this struct
+///
+/// # Example
+/// ```rust
+/// # use new_string_template::template::Template;
+/// # use new_string_template_macros::TemplateData;
+/// #[derive(TemplateData)]
+/// struct Meta {
+///     id:    u64,
+///     admin: bool,
+/// }
+///
+/// #[derive(TemplateData)]
+/// struct Message {
+///     #[template(rename = "user")]
+///     username: String,
+///     #[template(skip)]
+///     #[allow(dead_code)]
+///     password: String,
+///     #[template(flatten)]
+///     meta:     Meta,
+/// }
+///
+/// let templ = Template::new("{user} ({id:03}){#if admin} admin{/if} {password}").with_format_specs();
+/// let data = Message {
+///     username: "some".into(),
+///     password: "secret".into(),
+///     meta:     Meta { id: 10, admin: false },
+/// };
+///
+/// assert_eq!("some (010) {password}", templ.render_nofail_with(&data));
+/// ```
+#[proc_macro_derive(TemplateData, attributes(template))]
+pub fn derive_template_data(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    return expand_template_data(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into();
+}
+
+/// The input of [`template!`]: a string literal and optionally a struct to check against
+struct TemplateInput {
+    src:      LitStr,
+    data:     Option<Path>,
+    /// The generic parameters declared before `data`, like `<T>` in `<T> Data<T>`
+    generics: Generics,
+}
+
+impl Parse for TemplateInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let src = input.parse()?;
+        let mut data = None;
+        let mut generics = Generics::default();
+
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            generics = input.parse()?;
+            data = Some(input.parse()?);
+            input.parse::<Option<Token![,]>>()?;
+        }
+
+        return Ok(Self { src, data, generics });
+    }
+}
+
+/// Generate the code for [`template!`]
+fn expand_template(input: &TemplateInput) -> syn::Result<TokenStream2> {
+    let src = &input.src;
+    let templ = Template::try_new(src.value()).map_err(|err| return syn::Error::new(src.span(), err))?;
+
+    let check = match &input.data {
+        Some(data) => {
+            let fields = field_members(&templ, src)?;
+            let (impl_generics, _, where_clause) = input.generics.split_for_impl();
+
+            quote! {
+                #[allow(unused)]
+                fn __check_fields #impl_generics (data: &#data) #where_clause {
+                    #( let _ = &data.#fields; )*
+                }
+            }
+        },
+        None => TokenStream2::new(),
+    };
+
+    return Ok(quote! {
+        {
+            #check
+            ::new_string_template::template::Template::try_new(#src)
+                .expect("Expected Template to be valid, because it was checked at compile time")
+        }
+    });
+}
+
+/// Get the fields required by the placeholders and sections of `templ` (see [`Template::required_keys`]), deduplicated and with the span of `src`
+fn field_members(templ: &Template, src: &LitStr) -> syn::Result<Vec<Member>> {
+    let mut members: Vec<Member> = Vec::new();
+
+    for name in templ.required_keys() {
+        let field = &name[..name.find(|c| return c == '.' || c == '[').unwrap_or(name.len())];
+
+        if field.is_empty() {
+            continue;
+        }
+
+        let member = if let Ok(index) = field.parse::<u32>() {
+            Member::Unnamed(Index {
+                index,
+                span: src.span(),
+            })
+        } else {
+            let mut ident = syn::parse_str::<Ident>(field).map_err(|_| {
+                return syn::Error::new(
+                    src.span(),
+                    format!("Placeholder \"{}\" is not a valid field name", name),
+                );
+            })?;
+            ident.set_span(src.span());
+
+            Member::Named(ident)
+        };
+
+        if !members.contains(&member) {
+            members.push(member);
+        }
+    }
+
+    return Ok(members);
+}
+
+/// The options of a field for [`derive_template_data`], from its `#[template(...)]` attributes
+#[derive(Default)]
+struct FieldOptions {
+    rename:  Option<LitStr>,
+    skip:    bool,
+    flatten: bool,
+}
+
+impl FieldOptions {
+    /// Parse the `#[template(...)]` attributes of `field`
+    fn from_field(field: &syn::Field) -> syn::Result<Self> {
+        let mut options = Self::default();
+
+        for attr in field.attrs.iter().filter(|v| return v.path().is_ident("template")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    options.rename = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("skip") {
+                    options.skip = true;
+                } else if meta.path.is_ident("flatten") {
+                    options.flatten = true;
+                } else {
+                    return Err(meta.error("Unknown template attribute, expected \"rename\", \"skip\" or \"flatten\""));
+                }
+
+                return Ok(());
+            })?;
+        }
+
+        if options.flatten && (options.skip || options.rename.is_some()) {
+            return Err(syn::Error::new_spanned(
+                field,
+                "\"flatten\" cannot be used together with \"rename\" or \"skip\"",
+            ));
+        }
+
+        return Ok(options);
+    }
+}
+
+/// Generate the code for [`derive_template_data`]
+fn expand_template_data(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "TemplateData can only be derived for structs",
+            ))
+        },
+    };
+
+    let mut values = Vec::new();
+    let mut numbers = Vec::new();
+    let mut bools = Vec::new();
+    let mut flattened = Vec::new();
+
+    for (index, field) in fields.iter().enumerate() {
+        let options = FieldOptions::from_field(field)?;
+
+        if options.skip {
+            continue;
+        }
+
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(Index::from(index)),
+        };
+
+        if options.flatten {
+            flattened.push(member);
+            continue;
+        }
+
+        let name = match (&options.rename, &field.ident) {
+            (Some(rename), _) => rename.value(),
+            (None, Some(ident)) => ident.to_string(),
+            (None, None) => index.to_string(),
+        };
+
+        // strings are borrowed, all other fields are converted with "ToString"
+        let value = match &field.ty {
+            Type::Reference(reference) if primitive_name(&reference.elem).as_deref() == Some("str") => {
+                quote!(::std::borrow::Cow::Borrowed(self.#member))
+            },
+            ty if primitive_name(ty).as_deref() == Some("String") => {
+                quote!(::std::borrow::Cow::Borrowed(::std::string::String::as_str(&self.#member)))
+            },
+            _ => quote!(::std::borrow::Cow::Owned(::std::string::ToString::to_string(&self.#member))),
+        };
+
+        values.push(quote! {
+            #name => return ::core::option::Option::Some(#value),
+        });
+
+        match primitive_name(&field.ty).as_deref() {
+            Some("bool") => bools.push(quote! {
+                #name => return ::core::option::Option::Some(self.#member),
+            }),
+            Some("f32" | "f64") => numbers.push(quote! {
+                #name => return ::core::option::Option::Some(::new_string_template::value::Number::Float(::core::convert::From::from(self.#member))),
+            }),
+            Some("i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64" | "u128" | "usize") => {
+                numbers.push(quote! {
+                    #name => return ::core::convert::TryFrom::try_from(self.#member).ok().map(::new_string_template::value::Number::Integer),
+                });
+            },
+            _ => (),
+        }
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    return Ok(quote! {
+        impl #impl_generics ::new_string_template::source::ValueSource for #ident #ty_generics #where_clause {
+            fn get_value(&self, name: &str) -> ::core::option::Option<::std::borrow::Cow<'_, str>> {
+                match name {
+                    #( #values )*
+                    _ => (),
+                }
+
+                #(
+                    if let ::core::option::Option::Some(v) = ::new_string_template::source::ValueSource::get_value(&self.#flattened, name) {
+                        return ::core::option::Option::Some(v);
+                    }
+                )*
+
+                return ::core::option::Option::None;
+            }
+
+            fn get_list(&self, name: &str) -> ::core::option::Option<::std::vec::Vec<&dyn ::new_string_template::source::ValueSource>> {
+                #(
+                    if let ::core::option::Option::Some(v) = ::new_string_template::source::ValueSource::get_list(&self.#flattened, name) {
+                        return ::core::option::Option::Some(v);
+                    }
+                )*
+
+                return ::core::option::Option::None;
+            }
+
+            fn get_child(&self, name: &str) -> ::core::option::Option<&dyn ::new_string_template::source::ValueSource> {
+                #(
+                    if let ::core::option::Option::Some(v) = ::new_string_template::source::ValueSource::get_child(&self.#flattened, name) {
+                        return ::core::option::Option::Some(v);
+                    }
+                )*
+
+                return ::core::option::Option::None;
+            }
+
+            fn get_number(&self, name: &str) -> ::core::option::Option<::new_string_template::value::Number> {
+                match name {
+                    #( #numbers )*
+                    _ => (),
+                }
+
+                #(
+                    if let ::core::option::Option::Some(v) = ::new_string_template::source::ValueSource::get_number(&self.#flattened, name) {
+                        return ::core::option::Option::Some(v);
+                    }
+                )*
+
+                return ::core::option::Option::None;
+            }
+
+            fn get_bool(&self, name: &str) -> ::core::option::Option<bool> {
+                match name {
+                    #( #bools )*
+                    _ => (),
+                }
+
+                #(
+                    if let ::core::option::Option::Some(v) = ::new_string_template::source::ValueSource::get_bool(&self.#flattened, name) {
+                        return ::core::option::Option::Some(v);
+                    }
+                )*
+
+                return ::core::option::Option::None;
+            }
+        }
+    });
+}
+
+/// Get the name of `ty` if it is a single identifier (like `bool`, `u32` or `String`), which is used to find the fields with typed values
+///
+/// Type aliases and paths like `std::primitive::bool` are not detected, so those fields only provide their string value (converted with [`ToString`]).
+fn primitive_name(ty: &Type) -> Option<String> {
+    return match ty {
+        Type::Path(path) if path.qself.is_none() => path.path.get_ident().map(ToString::to_string),
+        _ => None,
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn expand(input: TokenStream2) -> String {
+        let input: TemplateInput = syn::parse2(input).expect("Expected input to parse");
+
+        return expand_template(&input)
+            .unwrap_or_else(syn::Error::into_compile_error)
+            .to_string();
+    }
+
+    #[test]
+    fn test_expand() {
+        let expanded = expand(quote!(
+            "Hello {name} {user.name} {0} {#if show}{#each jobs}{job}{#if ok}{/if}{/each}{/if}",
+            Data
+        ));
+
+        assert!(expanded.contains("fn __check_fields (data : & Data)"));
+        assert!(expanded.contains("let _ = & data . name ;"));
+        assert!(expanded.contains("let _ = & data . user ;"));
+        assert!(expanded.contains("let _ = & data . 0 ;"));
+        assert!(expanded.contains("let _ = & data . show ;"));
+        assert!(expanded.contains("let _ = & data . jobs ;"));
+        assert!(!expanded.contains("job ;"));
+        assert!(!expanded.contains("ok ;"));
+        assert!(!expanded.contains("compile_error"));
+    }
+
+    #[test]
+    fn test_expand_without_data() {
+        let expanded = expand(quote!("Hello {name}"));
+
+        assert!(!expanded.contains("__check_fields"));
+        assert!(expanded.contains("Template :: try_new (\"Hello {name}\")"));
+    }
+
+    #[test]
+    fn test_expand_defaults_and_generics() {
+        let expanded = expand(quote!("Hello {name} {nick:-anon} {name:-other}", <T: Clone> Data<T>));
+
+        assert!(expanded.contains("fn __check_fields < T : Clone > (data : & Data < T >)"));
+        assert!(expanded.contains("let _ = & data . name ;"));
+        assert!(!expanded.contains("nick ;"));
+
+        let expanded = expand(quote!("Hello {name}", Data<'static, String>));
+        assert!(expanded.contains("fn __check_fields (data : & Data < 'static , String >)"));
+    }
+
+    #[test]
+    fn test_expand_errors() {
+        let expanded = expand(quote!("Hello {#if name}"));
+        assert!(expanded.contains("compile_error"));
+        assert!(expanded.contains("Found \\\"{#if}\\\" without \\\"{/if}\\\" at 1:7"));
+
+        let expanded = expand(quote!("Hello {type}", Data));
+        assert!(expanded.contains("Placeholder \\\"type\\\" is not a valid field name"));
+    }
+
+    fn expand_derive(input: TokenStream2) -> String {
+        let input: DeriveInput = syn::parse2(input).expect("Expected input to parse");
+
+        return expand_template_data(&input)
+            .unwrap_or_else(syn::Error::into_compile_error)
+            .to_string();
+    }
+
+    #[test]
+    fn test_expand_derive() {
+        let expanded = expand_derive(quote! {
+            struct Data {
+                name: String,
+                #[template(rename = "other")]
+                renamed: u32,
+                #[template(skip)]
+                skipped: String,
+                #[template(flatten)]
+                meta: Meta,
+            }
+        });
+
+        assert!(expanded.contains("\"name\" => return"));
+        assert!(expanded.contains("\"other\" => return"));
+        assert!(!expanded.contains("\"renamed\""));
+        assert!(!expanded.contains("skipped"));
+        assert!(expanded.contains("get_value (& self . meta , name)"));
+        assert!(expanded.contains("get_list (& self . meta , name)"));
+        assert!(!expanded.contains("compile_error"));
+
+        let expanded = expand_derive(quote! {
+            struct Data {
+                admin: bool,
+                age: u8,
+                score: f64,
+                name: String,
+                #[template(flatten)]
+                meta: Meta,
+            }
+        });
+        assert!(expanded.contains("\"admin\" => return :: core :: option :: Option :: Some (self . admin)"));
+        assert!(expanded.contains("\"age\" => return :: core :: convert :: TryFrom :: try_from (self . age)"));
+        assert!(expanded.contains("\"score\" => return :: core :: option :: Option :: Some (:: new_string_template :: value :: Number :: Float"));
+        assert!(!expanded.contains("Some (self . name)"));
+        assert!(!expanded.contains("try_from (self . name)"));
+        assert!(expanded.contains("get_number (& self . meta , name)"));
+        assert!(expanded.contains("get_bool (& self . meta , name)"));
+
+        let expanded = expand_derive(quote! {
+            struct Data<'a> {
+                name: String,
+                title: &'a str,
+                age: u8,
+            }
+        });
+        assert!(expanded.contains("\"name\" => return :: core :: option :: Option :: Some (:: std :: borrow :: Cow :: Borrowed (:: std :: string :: String :: as_str (& self . name)))"));
+        assert!(expanded.contains("\"title\" => return :: core :: option :: Option :: Some (:: std :: borrow :: Cow :: Borrowed (self . title))"));
+        assert!(expanded.contains("\"age\" => return :: core :: option :: Option :: Some (:: std :: borrow :: Cow :: Owned (:: std :: string :: ToString :: to_string (& self . age)))"));
+
+        let expanded = expand_derive(quote! {
+            struct Data(String, #[template(skip)] String);
+        });
+        assert!(expanded.contains("\"0\" => return"));
+        assert!(!expanded.contains("\"1\""));
+    }
+
+    #[test]
+    fn test_expand_derive_errors() {
+        let expanded = expand_derive(quote! {
+            enum Data {}
+        });
+        assert!(expanded.contains("TemplateData can only be derived for structs"));
+
+        let expanded = expand_derive(quote! {
+            struct Data {
+                #[template(unknown)]
+                name: String,
+            }
+        });
+        assert!(expanded.contains("Unknown template attribute"));
+
+        let expanded = expand_derive(quote! {
+            struct Data {
+                #[template(flatten, skip)]
+                name: String,
+            }
+        });
+        assert!(expanded.contains("cannot be used together"));
+    }
+}
@@ -1,18 +1,130 @@
 //! Module for the Error of this Crate
 
-use std::error;
+use std::{
+    error,
+    fmt,
+    ops::Range,
+};
 
 /// The Error for this Crate
 #[derive(Debug)]
 pub struct TemplateError {
-    kind:  TemplateErrorKind,
-    error: Box<dyn error::Error + Send + Sync>,
+    kind:        TemplateErrorKind,
+    error:       Box<dyn error::Error + Send + Sync>,
+    /// Position of the offending placeholder in the Template String, if the error is about a placeholder
+    span:        Option<Span>,
+    /// Name of the offending placeholder, if the error is about a placeholder
+    placeholder: Option<String>,
+    /// All the collected errors, if this is a [`TemplateErrorKind::Multiple`] error
+    errors:      Vec<TemplateError>,
+}
+
+/// Position of a match in a Template String
+///
+/// Stores the byte offsets and the (1-based) line and column of the beginning of the match
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Span {
+    /// Offset for the beginning of the match
+    start:  usize,
+    /// Offset for the end of the match
+    /// This refers to the character just after the match
+    end:    usize,
+    /// Line of the beginning of the match, starting at "1"
+    line:   usize,
+    /// Column (in characters) of the beginning of the match, starting at "1"
+    column: usize,
+}
+
+impl Span {
+    /// Create a new [`Span`] for the byte range `start..end` in `src`, calculating the line and column of `start`
+    pub(crate) fn new(src: &str, start: usize, end: usize) -> Self {
+        let before = &src[..start];
+        let line = before.matches('\n').count() + 1;
+        // the column is counted from the last newline (or the beginning of the string if there is none)
+        let line_start = before.rfind('\n').map_or(0, |i| return i + 1);
+        let column = before[line_start..].chars().count() + 1;
+
+        return Self {
+            start,
+            end,
+            line,
+            column,
+        };
+    }
+
+    /// Get the byte offset of the beginning of the match
+    #[must_use]
+    pub const fn start(&self) -> usize {
+        return self.start;
+    }
+
+    /// Get the byte offset just after the end of the match
+    #[must_use]
+    pub const fn end(&self) -> usize {
+        return self.end;
+    }
+
+    /// Get the byte range of the match, usable to index the Template String
+    #[must_use]
+    pub const fn range(&self) -> Range<usize> {
+        return self.start..self.end;
+    }
+
+    /// Get the line of the beginning of the match, starting at "1"
+    #[must_use]
+    pub const fn line(&self) -> usize {
+        return self.line;
+    }
+
+    /// Get the column (in characters) of the beginning of the match, starting at "1"
+    #[must_use]
+    pub const fn column(&self) -> usize {
+        return self.column;
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "{}:{}", self.line, self.column);
+    }
 }
 
 /// The Error kind for [`TemplateError`]
+///
+/// New kinds may be added in minor versions, so matches on it need a wildcard arm.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum TemplateErrorKind {
+    /// No value was provided for a placeholder
     MissingData,
+    /// Writing into a [`std::fmt::Write`] failed
+    Fmt,
+    /// Writing into a [`std::io::Write`] failed
+    Io,
+    /// Multiple errors were collected, see [`TemplateError::errors`]
+    Multiple,
+    /// A filter used in a placeholder does not exist
+    UnknownFilter,
+    /// A filter used in a placeholder returned a error
+    Filter,
+    /// The Template String could not be parsed
+    InvalidSyntax,
+    /// A segment of a path like `{user.address.city}` is used as a map or list, but is not one
+    NotAContainer,
+    /// The value for a placeholder is a map or list, which cannot be rendered
+    NotAScalar,
+    /// A value could not be serialized, only used with the `serde` feature
+    Serialize,
+    /// A custom regex cannot be used to find placeholders, only used with the `custom-regex` feature
+    InvalidRegex,
+    /// A positional placeholder (like `{2}` or the third `{}`) has no value, because there are not enough values
+    IndexOutOfRange,
+    /// Positional placeholders with index (`{0}`) and without index (`{}`) are used in the same template
+    MixedPositional,
+    /// A placeholder has a numeric format spec (like `{id:08x}` or `{price:+.2}`), but its value is not a (fitting) number
+    NotANumber,
+    /// The value for a repeated section (`{#each list}`) is provided, but is not a list
+    NotAList,
 }
 
 impl TemplateError {
@@ -24,30 +136,104 @@ impl TemplateError {
         return Self {
             kind,
             error: error.into(),
+            span: None,
+            placeholder: None,
+            errors: Vec::new(),
         };
     }
 
+    /// Create a new [`TemplateErrorKind::Multiple`] Error instance from all the collected `errors`
+    pub fn new_multiple<E>(error: E, errors: Vec<TemplateError>) -> Self
+    where
+        E: Into<Box<dyn error::Error + Send + Sync>>,
+    {
+        let mut new = Self::new(TemplateErrorKind::Multiple, error);
+        new.errors = errors;
+
+        return new;
+    }
+
+    /// Set the [`Span`] of the placeholder this Error is about
+    #[must_use]
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = Some(span);
+
+        return self;
+    }
+
+    /// Set the name of the placeholder this Error is about
+    #[must_use]
+    pub fn with_placeholder<T: Into<String>>(mut self, name: T) -> Self {
+        self.placeholder = Some(name.into());
+
+        return self;
+    }
+
     /// Get the [`TemplateErrorKind`] that his Error instance is
     #[must_use]
     pub const fn kind(&self) -> TemplateErrorKind {
         return self.kind;
     }
+
+    /// Get the [`Span`] of the placeholder this Error is about, if any
+    #[must_use]
+    pub const fn span(&self) -> Option<Span> {
+        return self.span;
+    }
+
+    /// Get the name of the placeholder this Error is about, if any
+    #[must_use]
+    pub fn placeholder(&self) -> Option<&str> {
+        return self.placeholder.as_deref();
+    }
+
+    /// Get all the collected errors, only non-empty for [`TemplateErrorKind::Multiple`]
+    #[must_use]
+    pub fn errors(&self) -> &[TemplateError] {
+        return &self.errors;
+    }
 }
 
 impl std::fmt::Display for TemplateError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        return write!(
+        write!(
             f,
             "{}",
             match &self.kind {
                 TemplateErrorKind::MissingData => format!("MissingData: {}", self.error),
+                TemplateErrorKind::Fmt => format!("Fmt: {}", self.error),
+                TemplateErrorKind::Io => format!("Io: {}", self.error),
+                TemplateErrorKind::Multiple => format!("Multiple: {}", self.error),
+                TemplateErrorKind::UnknownFilter => format!("UnknownFilter: {}", self.error),
+                TemplateErrorKind::Filter => format!("Filter: {}", self.error),
+                TemplateErrorKind::InvalidSyntax => format!("InvalidSyntax: {}", self.error),
+                TemplateErrorKind::NotAContainer => format!("NotAContainer: {}", self.error),
+                TemplateErrorKind::NotAScalar => format!("NotAScalar: {}", self.error),
+                TemplateErrorKind::Serialize => format!("Serialize: {}", self.error),
+                TemplateErrorKind::InvalidRegex => format!("InvalidRegex: {}", self.error),
+                TemplateErrorKind::IndexOutOfRange => format!("IndexOutOfRange: {}", self.error),
+                TemplateErrorKind::MixedPositional => format!("MixedPositional: {}", self.error),
+                TemplateErrorKind::NotANumber => format!("NotANumber: {}", self.error),
+                TemplateErrorKind::NotAList => format!("NotAList: {}", self.error),
             }
-        );
+        )?;
+
+        if let Some(span) = &self.span {
+            write!(f, " at {}", span)?;
+        }
+
+        return Ok(());
     }
 }
 
 impl error::Error for TemplateError {}
 
+impl From<fmt::Error> for TemplateError {
+    fn from(err: fmt::Error) -> Self {
+        return Self::new(TemplateErrorKind::Fmt, err);
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -65,4 +251,52 @@ mod test {
 
         assert_eq!(TemplateErrorKind::MissingData, missing_data_error.kind());
     }
+
+    #[test]
+    fn test_display_with_span() {
+        let src = "line 1\nline {data1}";
+        let missing_data_error = TemplateError::new(TemplateErrorKind::MissingData, "SomeText")
+            .with_span(Span::new(src, 12, 19))
+            .with_placeholder("data1");
+
+        assert_eq!("MissingData: SomeText at 2:6", format!("{}", missing_data_error));
+        assert_eq!(Some("data1"), missing_data_error.placeholder());
+    }
+
+    #[test]
+    fn test_span_line_column() {
+        let src = "first {a}\nsecond\n  äö {b}";
+
+        let span = Span::new(src, 6, 9);
+        assert_eq!((1, 7), (span.line(), span.column()));
+        assert_eq!("{a}", &src[span.range()]);
+
+        let start = src.rfind('{').unwrap();
+        let span = Span::new(src, start, src.len());
+        // "ä" and "ö" are 2 bytes each, but only count as one column
+        assert_eq!((3, 6), (span.line(), span.column()));
+        assert_eq!("{b}", &src[span.range()]);
+    }
+
+    #[test]
+    fn test_multiple() {
+        let multiple_error = TemplateError::new_multiple(
+            "2 Errors",
+            vec![
+                TemplateError::new(TemplateErrorKind::MissingData, "SomeText").with_placeholder("data1"),
+                TemplateError::new(TemplateErrorKind::MissingData, "SomeText").with_placeholder("data2"),
+            ],
+        );
+
+        assert_eq!(TemplateErrorKind::Multiple, multiple_error.kind());
+        assert_eq!("Multiple: 2 Errors", format!("{}", multiple_error));
+        assert_eq!(
+            vec![Some("data1"), Some("data2")],
+            multiple_error
+                .errors()
+                .iter()
+                .map(|v| return v.placeholder())
+                .collect::<Vec<_>>()
+        );
+    }
 }
@@ -0,0 +1,371 @@
+//! Module for the filters that can be applied to values, like `{data1 | upper | truncate(10)}`
+
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    error,
+    fmt,
+    sync::Arc,
+};
+
+/// The Result a filter function returns
+pub type FilterResult = Result<String, Box<dyn error::Error + Send + Sync>>;
+
+/// The type of a filter function, which gets the current value and the arguments given in the template
+pub type FilterFn = dyn Fn(&str, &[FilterArg]) -> FilterResult + Send + Sync;
+
+/// A argument given to a filter in the template, like the `10` in `{data1 | truncate(10)}`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterArg {
+    /// The argument was a integer, like `10` or `-1`
+    Integer(i64),
+    /// The argument was a string, either quoted like `"some text"` or a bare word like `text`
+    String(String),
+}
+
+impl FilterArg {
+    /// Parse a single (already trimmed) argument
+    fn parse(arg: &str) -> Self {
+        if let Ok(v) = arg.parse::<i64>() {
+            return Self::Integer(v);
+        }
+
+        // remove quotes if the whole argument is quoted
+        if arg.len() >= 2 && arg.starts_with('"') && arg.ends_with('"') {
+            return Self::String(arg[1..arg.len() - 1].to_string());
+        }
+
+        return Self::String(arg.to_string());
+    }
+
+    /// Get the argument as a integer, if it is one
+    #[must_use]
+    pub fn as_integer(&self) -> Option<i64> {
+        return match self {
+            Self::Integer(v) => Some(*v),
+            Self::String(_) => None,
+        };
+    }
+
+    /// Get the argument as a string, if it is one
+    #[must_use]
+    pub fn as_str(&self) -> Option<&str> {
+        return match self {
+            Self::Integer(_) => None,
+            Self::String(v) => Some(v),
+        };
+    }
+}
+
+/// A single filter used in a placeholder, like `truncate(10)` in `{data1 | truncate(10)}`
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct FilterCall {
+    /// Offset for the beginning of the filter name in a parsed Template String
+    pub(crate) name_start: usize,
+    /// Offset for the end of the filter name in a parsed Template String
+    /// This refers to the character just after the name
+    pub(crate) name_end:   usize,
+    /// All the arguments given to the filter
+    pub(crate) args:       Vec<FilterArg>,
+}
+
+/// Parse a filter chain (like `| upper | truncate(10)`) that starts at `offset` in the Template String
+///
+/// The chain is expected to already be validated by the scanner, so this function does not error.
+pub(crate) fn parse_filters(chain: &str, offset: usize) -> Vec<FilterCall> {
+    let mut filters = Vec::new();
+
+    for (part_start, part) in split_unquoted(chain, '|') {
+        let part_offset = offset + part_start;
+        let trimmed = part.trim_start();
+        let name_start = part_offset + (part.len() - trimmed.len());
+        let trimmed = trimmed.trim_end();
+
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        let (name, args) = match trimmed.find('(') {
+            Some(i) => (&trimmed[..i], parse_args(&trimmed[i + 1..trimmed.len() - 1])),
+            None => (trimmed, Vec::new()),
+        };
+
+        filters.push(FilterCall {
+            name_start,
+            name_end: name_start + name.len(),
+            args,
+        });
+    }
+
+    return filters;
+}
+
+/// Parse the arguments of a filter (the part between the parentheses)
+fn parse_args(args: &str) -> Vec<FilterArg> {
+    if args.trim().is_empty() {
+        return Vec::new();
+    }
+
+    return split_unquoted(args, ',')
+        .into_iter()
+        .map(|(_, arg)| return FilterArg::parse(arg.trim()))
+        .collect();
+}
+
+/// Split `value` at every `separator` that is not inside double quotes or parentheses
+///
+/// Returns every part together with its offset in `value`.
+fn split_unquoted(value: &str, separator: char) -> Vec<(usize, &str)> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut quoted = false;
+    let mut depth = 0_usize;
+
+    for (i, c) in value.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '(' if !quoted => depth += 1,
+            ')' if !quoted => depth = depth.saturating_sub(1),
+            _ if c == separator && !quoted && depth == 0 => {
+                parts.push((start, &value[start..i]));
+                start = i + c.len_utf8();
+            },
+            _ => (),
+        }
+    }
+
+    parts.push((start, &value[start..]));
+
+    return parts;
+}
+
+/// Store for custom filters added with [`Template::register_filter`](crate::template::Template::register_filter)
+#[derive(Clone, Default)]
+pub(crate) struct FilterRegistry {
+    filters: HashMap<String, Arc<FilterFn>>,
+}
+
+impl FilterRegistry {
+    /// Add (or replace) the filter `name`
+    pub(crate) fn insert(&mut self, name: String, filter: Arc<FilterFn>) {
+        self.filters.insert(name, filter);
+    }
+
+    /// Apply the filter `name` to `value`, custom filters take precedence over the built-in filters
+    ///
+    /// Returns [`None`] if there is no filter with that name
+    pub(crate) fn apply(&self, name: &str, value: &str, args: &[FilterArg]) -> Option<FilterResult> {
+        if let Some(filter) = self.filters.get(name) {
+            return Some(filter(value, args));
+        }
+
+        return builtin(name).map(|filter| return filter(value, args));
+    }
+}
+
+impl fmt::Debug for FilterRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut names: Vec<&String> = self.filters.keys().collect();
+        names.sort();
+
+        return f.debug_struct("FilterRegistry").field("filters", &names).finish();
+    }
+}
+
+impl PartialEq for FilterRegistry {
+    fn eq(&self, other: &Self) -> bool {
+        return self.filters.len() == other.filters.len()
+            && self.filters.iter().all(|(name, filter)| {
+                return other
+                    .filters
+                    .get(name)
+                    .map_or(false, |other_filter| return Arc::ptr_eq(filter, other_filter));
+            });
+    }
+}
+
+/// Get the built-in filter `name`
+///
+/// The built-in filters are:
+/// - `upper`: convert the value to uppercase
+/// - `lower`: convert the value to lowercase
+/// - `trim`, `trim_start`, `trim_end`: remove whitespace from the value
+/// - `truncate(len)`: shorten the value to at most `len` characters
+/// - `pad_left(len, fill)`, `pad_right(len, fill)`: pad the value to at least `len` characters with the characters of `fill` (default `" "`, can also be a single digit like `0`)
+fn builtin(name: &str) -> Option<fn(&str, &[FilterArg]) -> FilterResult> {
+    return match name {
+        "upper" => Some(|v, _| return Ok(v.to_uppercase())),
+        "lower" => Some(|v, _| return Ok(v.to_lowercase())),
+        "trim" => Some(|v, _| return Ok(v.trim().to_string())),
+        "trim_start" => Some(|v, _| return Ok(v.trim_start().to_string())),
+        "trim_end" => Some(|v, _| return Ok(v.trim_end().to_string())),
+        "truncate" => Some(|v, args| {
+            let len = length_arg(args)?;
+
+            return Ok(v.chars().take(len).collect());
+        }),
+        "pad_left" => Some(|v, args| {
+            let padding = padding(v, args)?;
+
+            return Ok(padding + v);
+        }),
+        "pad_right" => Some(|v, args| {
+            let padding = padding(v, args)?;
+
+            return Ok(v.to_string() + &padding);
+        }),
+        _ => None,
+    };
+}
+
+/// Get the first argument as a length
+fn length_arg(args: &[FilterArg]) -> Result<usize, Box<dyn error::Error + Send + Sync>> {
+    return match args.first().and_then(FilterArg::as_integer) {
+        Some(len) if len >= 0 => Ok(len as usize),
+        _ => Err("Expected first argument to be a positive integer".into()),
+    };
+}
+
+/// Get the padding needed for `value` to have the length of the first argument, repeating the characters of the second argument as fill
+fn padding(value: &str, args: &[FilterArg]) -> Result<String, Box<dyn error::Error + Send + Sync>> {
+    let len = length_arg(args)?;
+    let fill: Cow<'_, str> = match args.get(1) {
+        Some(FilterArg::String(fill)) if !fill.is_empty() => Cow::Borrowed(fill),
+        // a unquoted digit like in "pad_left(5, 0)" is parsed as integer
+        Some(FilterArg::Integer(digit @ 0..=9)) => Cow::Owned(digit.to_string()),
+        Some(_) => return Err("Expected second argument to be a non-empty string or a single digit".into()),
+        None => Cow::Borrowed(" "),
+    };
+
+    return Ok(fill
+        .chars()
+        .cycle()
+        .take(len.saturating_sub(value.chars().count()))
+        .collect());
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_filters() {
+        let chain = " | upper |truncate( 10, \"a b\" ) | pad_left()";
+        let filters = parse_filters(chain, 5);
+
+        let found: Vec<_> = filters
+            .iter()
+            .map(|v| return (&chain[v.name_start - 5..v.name_end - 5], v.args.clone()))
+            .collect();
+        assert_eq!(
+            vec![
+                ("upper", vec![]),
+                (
+                    "truncate",
+                    vec![FilterArg::Integer(10), FilterArg::String("a b".to_string())]
+                ),
+                ("pad_left", vec![]),
+            ],
+            found
+        );
+    }
+
+    #[test]
+    fn test_parse_filters_quoted() {
+        let chain = " | pad_left(5, \",\") | pad_right(5, \"|\") | truncate(\"a | b\")";
+        let filters = parse_filters(chain, 0);
+
+        let found: Vec<_> = filters
+            .iter()
+            .map(|v| return (&chain[v.name_start..v.name_end], v.args.clone()))
+            .collect();
+        assert_eq!(
+            vec![
+                (
+                    "pad_left",
+                    vec![FilterArg::Integer(5), FilterArg::String(",".to_string())]
+                ),
+                (
+                    "pad_right",
+                    vec![FilterArg::Integer(5), FilterArg::String("|".to_string())]
+                ),
+                ("truncate", vec![FilterArg::String("a | b".to_string())]),
+            ],
+            found
+        );
+    }
+
+    #[test]
+    fn test_builtin() {
+        let registry = FilterRegistry::default();
+        let apply = |name: &str, value: &str, args: &[FilterArg]| {
+            return registry
+                .apply(name, value, args)
+                .expect("Expected filter to exist")
+                .expect("Expected filter to succeed");
+        };
+
+        assert_eq!("SOME", apply("upper", "Some", &[]));
+        assert_eq!("some", apply("lower", "Some", &[]));
+        assert_eq!("some", apply("trim", "  some ", &[]));
+        assert_eq!("some ", apply("trim_start", "  some ", &[]));
+        assert_eq!("  some", apply("trim_end", "  some ", &[]));
+        assert_eq!("äö", apply("truncate", "äöü", &[FilterArg::Integer(2)]));
+        assert_eq!("  ab", apply("pad_left", "ab", &[FilterArg::Integer(4)]));
+        assert_eq!(
+            "ab--",
+            apply(
+                "pad_right",
+                "ab",
+                &[FilterArg::Integer(4), FilterArg::String("-".into())]
+            )
+        );
+        assert_eq!("abc", apply("pad_right", "abc", &[FilterArg::Integer(2)]));
+        assert_eq!(
+            "ababx",
+            apply(
+                "pad_left",
+                "x",
+                &[FilterArg::Integer(5), FilterArg::String("ab".into())]
+            )
+        );
+        assert_eq!(
+            "xaba",
+            apply(
+                "pad_right",
+                "x",
+                &[FilterArg::Integer(4), FilterArg::String("ab".into())]
+            )
+        );
+
+        assert_eq!(
+            "00x",
+            apply("pad_left", "x", &[FilterArg::Integer(3), FilterArg::Integer(0)])
+        );
+        assert!(registry
+            .apply("pad_left", "x", &[FilterArg::Integer(3), FilterArg::Integer(10)])
+            .expect("Expected filter to exist")
+            .is_err());
+        assert!(registry
+            .apply("truncate", "some", &[])
+            .expect("Expected filter to exist")
+            .is_err());
+        assert!(registry.apply("unknown", "some", &[]).is_none());
+    }
+
+    #[test]
+    fn test_registry_custom() {
+        let mut registry = FilterRegistry::default();
+        registry.insert("upper".into(), Arc::new(|v, _| return Ok(format!("custom {}", v))));
+
+        assert_eq!(
+            "custom some",
+            registry
+                .apply("upper", "some", &[])
+                .expect("Expected filter to exist")
+                .expect("Expected filter to succeed")
+        );
+        assert_eq!(registry.clone(), registry);
+        assert_ne!(FilterRegistry::default(), registry);
+    }
+}
@@ -0,0 +1,310 @@
+//! Module for [`FormatSpec`], the `format!`-style specs of placeholders like `{name:>10}` or `{price:.2}`
+
+use crate::value::Number;
+
+/// The alignment of a value inside its width
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+/// A parsed format spec like `>10`, `.2` or `08x`, which uses the same syntax as [`format!`]:
+/// `[[fill]align][+][#][0][width][.precision][type]`, where type is one of `x`, `X`, `o`, `b`, `e` or `E`.
+///
+/// The `-` flag is not supported, because `:-` starts a default value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct FormatSpec {
+    fill:      char,
+    align:     Option<Align>,
+    plus:      bool,
+    alternate: bool,
+    zero:      bool,
+    width:     Option<usize>,
+    precision: Option<usize>,
+    kind:      Option<char>,
+}
+
+impl FormatSpec {
+    /// Parse `spec`, which is expected to already be validated by the scanner
+    pub(crate) fn parse(spec: &str) -> Self {
+        let mut result = Self {
+            fill:      ' ',
+            align:     None,
+            plus:      false,
+            alternate: false,
+            zero:      false,
+            width:     None,
+            precision: None,
+            kind:      None,
+        };
+        let mut rest = spec;
+
+        let mut chars = rest.chars();
+        match (chars.next(), chars.next().and_then(align)) {
+            (Some(fill), Some(align)) => {
+                result.fill = fill;
+                result.align = Some(align);
+                rest = &rest[fill.len_utf8() + 1..];
+            },
+            (Some(c), _) if self::align(c).is_some() => {
+                result.align = self::align(c);
+                rest = &rest[1..];
+            },
+            _ => (),
+        }
+
+        result.plus = eat(&mut rest, '+');
+        result.alternate = eat(&mut rest, '#');
+        result.zero = eat(&mut rest, '0');
+        result.width = eat_number(&mut rest);
+
+        if eat(&mut rest, '.') {
+            result.precision = eat_number(&mut rest);
+        }
+
+        result.kind = rest.chars().next();
+
+        return result;
+    }
+
+    /// Get whether this spec can only be used with numbers (it has a sign, `#`, `0`, a precision or a type)
+    pub(crate) fn is_numeric(&self) -> bool {
+        return self.plus || self.alternate || self.zero || self.precision.is_some() || self.kind.is_some();
+    }
+
+    /// Format `value`, which is formatted as `number` instead if it is one
+    ///
+    /// Returns the error message if the spec requires a number but `number` is [`None`],
+    /// or if the type is not supported for `number`.
+    pub(crate) fn format(&self, value: &str, number: Option<Number>) -> Result<String, String> {
+        let number = match number {
+            Some(v) => v,
+            None if self.is_numeric() => {
+                return Err("Format spec requires a number, but the value is not one".to_string())
+            },
+            None => return Ok(self.pad(value, Align::Left)),
+        };
+
+        let formatted = self.format_number(number)?;
+        // the sign and the prefix of "#" stay before the zeros of "0"
+        let sign_len = if formatted.starts_with('+') || formatted.starts_with('-') {
+            1
+        } else {
+            0
+        };
+        let prefix_len = if self.alternate && self.kind.map_or(false, |v| return v != 'e' && v != 'E') {
+            2
+        } else {
+            0
+        };
+        let non_finite = match number {
+            Number::Float(v) => !v.is_finite(),
+            Number::Integer(_) => false,
+        };
+
+        if self.zero && !non_finite {
+            let (prefix, digits) = formatted.split_at(sign_len + prefix_len);
+            let zeros = "0".repeat(self.width.unwrap_or(0).saturating_sub(formatted.chars().count()));
+
+            return Ok(format!("{}{}{}", prefix, zeros, digits));
+        }
+
+        return Ok(self.pad(&formatted, Align::Right));
+    }
+
+    /// Format `number` without padding
+    fn format_number(&self, number: Number) -> Result<String, String> {
+        let formatted = match (number, self.kind) {
+            (Number::Integer(v), None) => v.to_string(),
+            (Number::Integer(v), Some('x')) => format!("{}{:x}", self.prefix("0x"), v),
+            (Number::Integer(v), Some('X')) => format!("{}{:X}", self.prefix("0x"), v),
+            (Number::Integer(v), Some('o')) => format!("{}{:o}", self.prefix("0o"), v),
+            (Number::Integer(v), Some('b')) => format!("{}{:b}", self.prefix("0b"), v),
+            // exponents are formatted as float, because integers only support exponents since rust 1.42
+            (Number::Integer(v), Some(_)) => return self.format_number(Number::Float(v as f64)),
+            (Number::Float(v), None) => match self.precision {
+                Some(precision) => format!("{:.*}", precision, v),
+                None => v.to_string(),
+            },
+            (Number::Float(v), Some(kind @ 'e')) | (Number::Float(v), Some(kind @ 'E')) => {
+                let formatted = match self.precision {
+                    Some(precision) => format!("{:.*e}", precision, v),
+                    None => format!("{:e}", v),
+                };
+
+                with_kind(formatted, kind)
+            },
+            (Number::Float(_), Some(kind)) => return Err(format!("Format type \"{}\" requires an integer", kind)),
+        };
+
+        let is_nan = match number {
+            Number::Float(v) => v.is_nan(),
+            Number::Integer(_) => false,
+        };
+
+        if self.plus && !is_nan && !formatted.starts_with('-') {
+            return Ok(format!("+{}", formatted));
+        }
+
+        return Ok(formatted);
+    }
+
+    /// Get `prefix` if the `#` flag is set
+    fn prefix(&self, prefix: &'static str) -> &'static str {
+        return if self.alternate { prefix } else { "" };
+    }
+
+    /// Pad `value` to the width with the fill character, using `default` if there is no alignment
+    fn pad(&self, value: &str, default: Align) -> String {
+        let padding = self.width.unwrap_or(0).saturating_sub(value.chars().count());
+        let (before, after) = match self.align.unwrap_or(default) {
+            Align::Left => (0, padding),
+            Align::Center => (padding / 2, padding - padding / 2),
+            Align::Right => (padding, 0),
+        };
+
+        let mut out = String::with_capacity(value.len() + padding * self.fill.len_utf8());
+        out.extend(std::iter::repeat(self.fill).take(before));
+        out.push_str(value);
+        out.extend(std::iter::repeat(self.fill).take(after));
+
+        return out;
+    }
+}
+
+/// Get the [`Align`] for `c`, if it is one
+fn align(c: char) -> Option<Align> {
+    return match c {
+        '<' => Some(Align::Left),
+        '^' => Some(Align::Center),
+        '>' => Some(Align::Right),
+        _ => None,
+    };
+}
+
+/// Remove `c` from the start of `rest`, returning whether it was there
+fn eat(rest: &mut &str, c: char) -> bool {
+    if rest.starts_with(c) {
+        *rest = &rest[c.len_utf8()..];
+
+        return true;
+    }
+
+    return false;
+}
+
+/// Remove all ASCII digits from the start of `rest`, returning them as number if there were any
+fn eat_number(rest: &mut &str) -> Option<usize> {
+    let len = rest
+        .find(|c: char| return !c.is_ascii_digit())
+        .unwrap_or_else(|| return rest.len());
+    let (digits, remaining) = rest.split_at(len);
+    *rest = remaining;
+
+    return digits.parse().ok();
+}
+
+/// Use the case of `kind` for the exponent of `formatted`
+fn with_kind(formatted: String, kind: char) -> String {
+    if kind == 'E' {
+        return formatted.replace('e', "E");
+    }
+
+    return formatted;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Format `value` with `spec`
+    fn format(spec: &str, value: &str, number: Option<Number>) -> Result<String, String> {
+        return FormatSpec::parse(spec).format(value, number);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            FormatSpec {
+                fill:      '*',
+                align:     Some(Align::Center),
+                plus:      true,
+                alternate: true,
+                zero:      true,
+                width:     Some(12),
+                precision: Some(3),
+                kind:      Some('x'),
+            },
+            FormatSpec::parse("*^+#012.3x")
+        );
+        assert_eq!(Some(Align::Left), FormatSpec::parse("<").align);
+        assert_eq!(('<', Some(Align::Left)), {
+            let spec = FormatSpec::parse("<<");
+            (spec.fill, spec.align)
+        });
+        assert_eq!(Some(10), FormatSpec::parse("10").width);
+    }
+
+    #[test]
+    fn test_format_string() {
+        assert_eq!(
+            Ok(format!("{:>10}", "abc")),
+            format("ë>10", "abc", None).map(|v| return v.replace('ë', " "))
+        );
+        assert_eq!(
+            Ok(format!("{:10}|", "abc")),
+            format("10", "abc", None).map(|v| return v + "|")
+        );
+        assert_eq!(Ok(format!("{:*^9}", "abc")), format("*^9", "abc", None));
+        assert_eq!(Ok(format!("{:*^8}", "abc")), format("*^8", "abc", None));
+        assert_eq!(Ok("abc".to_string()), format("2", "abc", None));
+
+        for spec in &["+", "#", "05", ".2", ">5.2", "x", "e"] {
+            assert!(format(spec, "abc", None).is_err(), "Spec: {:?}", spec);
+        }
+    }
+
+    #[test]
+    fn test_format_integer() {
+        let int =
+            |spec: &str, v: i64| return format(spec, "", Some(Number::Integer(v))).expect("Expected Result to be Ok");
+
+        assert_eq!(format!("{:5}", 42), int("5", 42));
+        assert_eq!(format!("{:<5}", 42), int("<5", 42));
+        assert_eq!(format!("{:+}", 42), int("+", 42));
+        assert_eq!(format!("{:+}", -42), int("+", -42));
+        assert_eq!(format!("{:08}", -42), int("08", -42));
+        assert_eq!(format!("{:+08}", 42), int("+08", 42));
+        assert_eq!(format!("{:<08}", 42), int("<08", 42));
+        assert_eq!(format!("{:x}", 255), int("x", 255));
+        assert_eq!(format!("{:X}", 255), int("X", 255));
+        assert_eq!(format!("{:#X}", 255), int("#X", 255));
+        assert_eq!(format!("{:x}", -1i64), int("x", -1));
+        assert_eq!(format!("{:08x}", 255), int("08x", 255));
+        assert_eq!(format!("{:#010x}", 255), int("#010x", 255));
+        assert_eq!(format!("{:#o}", 8), int("#o", 8));
+        assert_eq!(format!("{:#b}", 5), int("#b", 5));
+        assert_eq!(format!("{:>#12b}", 5), int(">#12b", 5));
+        assert_eq!(format!("{:e}", 1234.0), int("e", 1234));
+        assert_eq!(format!("{:.1E}", 1234.0), int(".1E", 1234));
+        assert_eq!(format!("{:.2}", 5), int(".2", 5));
+    }
+
+    #[test]
+    fn test_format_float() {
+        let float = |spec: &str, v: f64| return format(spec, "", Some(Number::Float(v)));
+
+        assert_eq!(Ok(format!("{:.2}", 12.3456)), float(".2", 12.3456));
+        assert_eq!(Ok(format!("{:8.2}", 12.3456)), float("8.2", 12.3456));
+        assert_eq!(Ok(format!("{:08.2}", -12.3456)), float("08.2", -12.3456));
+        assert_eq!(Ok(format!("{:+.1}", 2.25)), float("+.1", 2.25));
+        assert_eq!(Ok(format!("{}", 1.5)), float("", 1.5));
+        assert_eq!(Ok(format!("{:e}", 1500.0)), float("e", 1500.0));
+        assert_eq!(Ok(format!("{:.2E}", 1500.0)), float(".2E", 1500.0));
+        assert_eq!(Ok(format!("{:+}", f64::NAN)), float("+", f64::NAN));
+        assert_eq!(Ok(format!("{:>6}", f64::INFINITY)), float("06", f64::INFINITY));
+        assert!(float("x", 1.5).is_err());
+    }
+}
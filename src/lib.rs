@@ -50,9 +50,11 @@ let rendered = templ.render_nofail(&data);
 assert_eq!("Something should be {data2}, and { not here }", rendered);
 ```
 
-Example with Custom Regex:
+Example with Custom Regex (requires the `custom-regex` feature, which is enabled by default):
 
 ```rust
+# #[cfg(feature = "custom-regex")]
+# {
 use new_string_template::template::Template;
 use std::collections::HashMap;
 use regex::Regex;
@@ -71,10 +73,42 @@ let data = {
 
 let rendered = templ.render_nofail(&data);
 assert_eq!("Something should be here, and here too", rendered);
+# }
 ```
 
-Note: with the default regex, a template-variable can have spaces or none at all.
+# Template syntax
+
+With the default syntax (other delimiters like `{{ name }}`, `${name}` or `%name%` can be used with `Template::with_syntax` and `Syntax`, or any regex with `Template::with_regex`):
+
+- Placeholders: `{data1}` is replaced by the value `data1`, spaces around the name are allowed (`{ data1 }`), but not inside of it.
+- Escapes: `{{` and `}}` are rendered as a literal `{` and `}`.
+- Defaults: `{data1:-default}` renders `default` if the value `data1` is missing.
+- Filters: `{data1 | upper | truncate(10)}` applies filters to the value, see `Template::register_filter` for all built-in filters.
+- Conditional sections: `{#if data1}...{#else}...{/if}` renders its content only if the value is provided and not empty, see `Template::try_new`.
+- Repeated sections: `{#each list sep=", "}...{/each}` renders its content once for every item of a list, see `Template::try_new`.
+- Paths: `{user.address.city}` or `{jobs[0].name}` look up nested values.
+- Positional placeholders: `{}` or `{0}` are rendered from a slice, like `format!`, see `Template::render_positional`.
+- Format specs: `{name:>10}`, `{price:.2}` or `{id:08x}` format the value like `format!`, after enabling them with `Template::with_format_specs`. Numeric specs (including a precision) require typed numbers.
+
+Lists, nested values and typed numbers require a source providing them, like `Value`.
+
+# Rendering APIs
+
+- `Template::render` and `Template::render_nofail` render from a `HashMap` of strings, `Template::render_with` and `Template::render_nofail_with` from any `ValueSource`.
+- `Template::render_values` renders typed values (strings, numbers, booleans, lists and maps) from a `HashMap` of `Value`s.
+- `Template::render_positional` and `Template::render_positional_values` render from a slice of values.
+- `Template::bind` binds the keys of a template rendered many times once, the returned `BoundTemplate` then renders from a slice of values without looking up any names.
+- `Template::render_serialize` renders any `Serialize` value with the `serde` feature, which also makes `Template` itself serializable and deserializable (as its template string).
+- The `new_string_template_macros` crate checks templates at compile time with `template!("Hello {name}")`, optionally also against the fields of a struct, and renders a struct directly with `#[derive(TemplateData)]`.
 */
 
 pub mod error;
+pub mod filter;
+mod format;
+mod scanner;
+#[cfg(feature = "serde")]
+mod serialize;
+pub mod source;
+pub mod syntax;
 pub mod template;
+pub mod value;
@@ -0,0 +1,555 @@
+//! Module for the scanner of the default syntax used by [`Template::new`](crate::template::Template::new)
+//!
+//! The scanner matches exactly like the following regex did (and still does in the tests), without needing a regex engine:
+//! ```txt
+//! (?mi)(?P<escape>\{\{|\}\})
+//! |\{\s*#if\s+(?P<if>\S+?)\s*\}
+//! |\{\s*#each\s+(?P<each>\S+?)(?:\s+sep\s*=\s*"(?P<sep>[^"]*)")?\s*\}
+//! |\{\s*(?P<else>#else)\s*\}
+//! |\{\s*(?P<end_if>/if)\s*\}
+//! |\{\s*(?P<end_each>/each)\s*\}
+//! |\{\s*(?P<name>\S+?)(?P<filters>(?:\s*\|\s*[A-Za-z_][A-Za-z0-9_]*(?:\([^\)\}]*\))?)*)\s*(?::-\s*(?P<default>[^\}]*?))?\s*\}
+//! |\{(?P<implicit>\s*)\}
+//! ```
+//!
+//! With format specs enabled (see [`Template::with_format_specs`](crate::template::Template::with_format_specs)),
+//! the placeholder part is instead:
+//! ```txt
+//! |\{\s*(?P<name>\S+?)(?P<filters>(?:\s*\|\s*[A-Za-z_][A-Za-z0-9_]*(?:\([^\)\}]*\))?)*)(?::(?P<spec>(?:[^\s\}\-]?[<>^])?\+?#?0?[0-9]*(?:\.[0-9]+)?(?-i:[xXobeE])?))?\s*(?::-\s*(?P<default>[^\}]*?))?\s*\}
+//! ```
+
+use crate::{
+    filter::parse_filters,
+    format::FormatSpec,
+    template::{
+        MatchEntry,
+        MatchKind,
+    },
+};
+
+/// Find all matches of the default syntax in `template`, in order
+///
+/// Placeholders only have format specs (like `{name:>10}`) if `format_specs` is set, otherwise `{a:b}` is the name `a:b`.
+pub(crate) fn scan(template: &str, format_specs: bool) -> Vec<MatchEntry> {
+    let mut matches = Vec::new();
+    let mut pos = 0;
+    // the number of "{}" found so far
+    let mut implicit = 0;
+
+    while let Some(offset) = template[pos..].find(|c| return c == '{' || c == '}') {
+        let start = pos + offset;
+
+        match scan_at(template, start, implicit, format_specs) {
+            Some(entry) => {
+                if entry.is_implicit() {
+                    implicit += 1;
+                }
+
+                pos = entry.full_match_end;
+                matches.push(entry);
+            },
+            // "{" and "}" are always 1 byte long
+            None => pos = start + 1,
+        }
+    }
+
+    return matches;
+}
+
+/// Try to match at `start` (which is a `{` or `}`), trying every kind of match in the same order as the regex
+///
+/// `implicit` is the position a `{}` would have.
+fn scan_at(src: &str, start: usize, implicit: usize, format_specs: bool) -> Option<MatchEntry> {
+    let rest = &src[start..];
+
+    if rest.starts_with("{{") || rest.starts_with("}}") {
+        return Some(MatchEntry::new_escape((start, start + 2), (start, start + 1)));
+    }
+
+    if !rest.starts_with('{') {
+        return None;
+    }
+
+    let inner = skip_ws(src, start + 1);
+
+    return scan_if(src, start, inner)
+        .or_else(|| return scan_each(src, start, inner))
+        .or_else(|| return scan_keyword(src, start, inner, "#else", MatchKind::Else))
+        .or_else(|| return scan_keyword(src, start, inner, "/if", MatchKind::EndIf))
+        .or_else(|| return scan_keyword(src, start, inner, "/each", MatchKind::EndEach))
+        .or_else(|| return scan_placeholder(src, start, inner, format_specs))
+        .or_else(|| {
+            // "{}", which only has whitespace inside
+            let end = char_at(src, inner, '}')?;
+
+            return Some(MatchEntry::new_kind(
+                MatchKind::Implicit(implicit),
+                (start, end),
+                (start + 1, inner),
+            ));
+        });
+}
+
+/// Match `{#if name}`
+fn scan_if(src: &str, start: usize, inner: usize) -> Option<MatchEntry> {
+    let name_start = skip_ws1(src, keyword(src, inner, "#if")?)?;
+    let (name_end, end) = lazy_name(src, name_start, |pos| return close(src, pos))?;
+
+    return Some(MatchEntry::new_kind(
+        MatchKind::If,
+        (start, end),
+        (name_start, name_end),
+    ));
+}
+
+/// Match `{#each name}` and `{#each name sep="separator"}`
+fn scan_each(src: &str, start: usize, inner: usize) -> Option<MatchEntry> {
+    let name_start = skip_ws1(src, keyword(src, inner, "#each")?)?;
+    let (name_end, (separator, end)) = lazy_name(src, name_start, |pos| {
+        // the separator is optional, but preferred
+        return separator(src, pos)
+            .map(|(separator, end)| return (Some(separator), end))
+            .or_else(|| return close(src, pos).map(|end| return (None, end)));
+    })?;
+
+    return Some(MatchEntry::new_kind(MatchKind::Each, (start, end), (name_start, name_end)).with_separator(separator));
+}
+
+/// Match the separator part of `{#each}` (` sep="separator"}`) at `pos`, including the closing bracket
+fn separator(src: &str, pos: usize) -> Option<((usize, usize), usize)> {
+    let pos = skip_ws(src, keyword(src, skip_ws1(src, pos)?, "sep")?);
+    let pos = skip_ws(src, char_at(src, pos, '=')?);
+    let sep_start = char_at(src, pos, '"')?;
+    let sep_end = sep_start + src[sep_start..].find('"')?;
+
+    return Some(((sep_start, sep_end), close(src, sep_end + 1)?));
+}
+
+/// Match a section tag without a value, like `{#else}`
+fn scan_keyword(src: &str, start: usize, inner: usize, word: &str, kind: MatchKind) -> Option<MatchEntry> {
+    let word_end = keyword(src, inner, word)?;
+    let end = close(src, word_end)?;
+
+    return Some(MatchEntry::new_kind(kind, (start, end), (inner, word_end)));
+}
+
+/// Match a placeholder like `{name | filter(arg):>10:-default}`, the format spec is only matched if `format_specs` is set
+fn scan_placeholder(src: &str, start: usize, inner: usize, format_specs: bool) -> Option<MatchEntry> {
+    let (name_end, (filters_end, spec, (default, end))) = lazy_name(src, inner, |pos| {
+        let filters_end = filters(src, pos);
+
+        // the format spec is optional, but preferred
+        let spec_start = char_at(src, filters_end, ':').filter(|_| return format_specs);
+        let with_spec = spec_start.and_then(|spec_start| {
+            return format_spec_ends(src, spec_start)
+                .into_iter()
+                .filter_map(|spec_end| {
+                    return placeholder_end(src, spec_end).map(|rest| return ((spec_start, spec_end), rest));
+                })
+                .next();
+        });
+
+        return match with_spec {
+            Some((spec, rest)) => Some((filters_end, Some(spec), rest)),
+            None => placeholder_end(src, filters_end).map(|rest| return (filters_end, None, rest)),
+        };
+    })?;
+    let format = spec
+        .filter(|(spec_start, spec_end)| return spec_start != spec_end)
+        .map(|(spec_start, spec_end)| return FormatSpec::parse(&src[spec_start..spec_end]));
+
+    return Some(
+        MatchEntry::new((start, end), (inner, name_end))
+            .with_default(default)
+            .with_filters(parse_filters(&src[name_end..filters_end], name_end))
+            .with_format(format),
+    );
+}
+
+/// Match the end of a placeholder (like ` :- default }`) at `pos`, returning the default value (if any) and the end of the closing bracket
+fn placeholder_end(src: &str, pos: usize) -> Option<(Option<(usize, usize)>, usize)> {
+    let pos = skip_ws(src, pos);
+
+    if src[pos..].starts_with(":-") {
+        let default_start = skip_ws(src, pos + 2);
+        let close = default_start + src[default_start..].find('}')?;
+        let default_end = default_start + src[default_start..close].trim_end().len();
+
+        return Some((Some((default_start, default_end)), close + 1));
+    }
+
+    return char_at(src, pos, '}').map(|end| return (None, end));
+}
+
+/// Find the possible ends of a format spec (like `>10` or `08x`) starting at `pos`, in the order the regex tries them
+///
+/// Only the longest spec for every way to match the fill and alignment is returned,
+/// because every shorter spec ends before a character of the spec, where a placeholder cannot end.
+fn format_spec_ends(src: &str, pos: usize) -> Vec<usize> {
+    let mut chars = src[pos..].chars();
+    let first = chars.next();
+    let second = chars.next();
+    let mut starts = Vec::with_capacity(3);
+
+    if let (Some(fill), Some(align)) = (first, second) {
+        if !fill.is_whitespace() && fill != '}' && fill != '-' && is_align(align) {
+            starts.push(pos + fill.len_utf8() + 1);
+        }
+    }
+
+    if first.map_or(false, is_align) {
+        starts.push(pos + 1);
+    }
+
+    starts.push(pos);
+
+    return starts
+        .into_iter()
+        .map(|start| return format_flags(src, start))
+        .collect();
+}
+
+/// Match as much of the part of a format spec after the alignment (like `+#08.2x`) as possible at `pos`, returning its end
+fn format_flags(src: &str, pos: usize) -> usize {
+    let mut end = pos;
+
+    for flag in &['+', '#', '0'] {
+        end = char_at(src, end, *flag).unwrap_or(end);
+    }
+
+    end = digits(src, end);
+
+    // the precision needs at least one digit
+    if let Some(precision_start) = char_at(src, end, '.') {
+        let precision_end = digits(src, precision_start);
+
+        if precision_end > precision_start {
+            end = precision_end;
+        }
+    }
+
+    if src[end..].starts_with(|c| return "xXobeE".contains(c)) {
+        end += 1;
+    }
+
+    return end;
+}
+
+/// Get whether `c` is an alignment of a format spec
+fn is_align(c: char) -> bool {
+    return c == '<' || c == '^' || c == '>';
+}
+
+/// Skip all ASCII digits starting at `pos`
+fn digits(src: &str, pos: usize) -> usize {
+    return src[pos..]
+        .find(|c: char| return !c.is_ascii_digit())
+        .map_or(src.len(), |i| return pos + i);
+}
+
+/// Match as many filters (like ` | truncate(10)`) as possible at `pos`, returning the end of the last filter
+fn filters(src: &str, pos: usize) -> usize {
+    let mut end = pos;
+
+    loop {
+        let pipe = skip_ws(src, end);
+        let name_start = match char_at(src, pipe, '|') {
+            Some(v) => skip_ws(src, v),
+            None => return end,
+        };
+
+        let mut chars = src[name_start..].char_indices();
+        let name_len = match chars.next() {
+            Some((_, c)) if is_ident_start(c) => chars
+                .find(|(_, c)| return !is_ident_start(*c) && !c.is_ascii_digit())
+                .map_or(src.len() - name_start, |(i, _)| return i),
+            _ => return end,
+        };
+        end = name_start + name_len;
+
+        // the arguments are optional, but preferred
+        if let Some(args_start) = char_at(src, end, '(') {
+            if let Some(i) = src[args_start..].find(|c| return c == ')' || c == '}') {
+                if src[args_start + i..].starts_with(')') {
+                    end = args_start + i + 1;
+                }
+            }
+        }
+    }
+}
+
+/// Get whether `chain` is a valid filter chain (like `upper | truncate(10)`), the leading `|` is optional
+///
+/// Used for the `filter` capture group of custom regexes, which are not checked by the scanner.
+#[cfg(feature = "custom-regex")]
+pub(crate) fn is_filter_chain(chain: &str) -> bool {
+    let chain = chain.trim();
+
+    if chain.is_empty() {
+        return true;
+    }
+
+    if chain.starts_with('|') {
+        return filters(chain, 0) == chain.len();
+    }
+
+    let chain = format!("|{}", chain);
+
+    return filters(&chain, 0) == chain.len();
+}
+
+/// Find the shortest name (only non-whitespace characters) starting at `start` for which `rest` matches after it
+///
+/// Returns the end of the name and the result of `rest`.
+fn lazy_name<T, F: Fn(usize) -> Option<T>>(src: &str, start: usize, rest: F) -> Option<(usize, T)> {
+    for (i, c) in src[start..].char_indices() {
+        if c.is_whitespace() {
+            return None;
+        }
+
+        let name_end = start + i + c.len_utf8();
+
+        if let Some(v) = rest(name_end) {
+            return Some((name_end, v));
+        }
+    }
+
+    return None;
+}
+
+/// Match optional whitespace and a closing bracket at `pos`, returning the end of the bracket
+fn close(src: &str, pos: usize) -> Option<usize> {
+    return char_at(src, skip_ws(src, pos), '}');
+}
+
+/// Match the character `expected` at `pos`, returning the position after it
+fn char_at(src: &str, pos: usize, expected: char) -> Option<usize> {
+    if src[pos..].starts_with(expected) {
+        return Some(pos + expected.len_utf8());
+    }
+
+    return None;
+}
+
+/// Match `word` case-insensitively at `pos`, returning the position after it
+///
+/// `word` has to be lowercase ASCII.
+fn keyword(src: &str, pos: usize, word: &str) -> Option<usize> {
+    let mut chars = src[pos..].char_indices();
+
+    for expected in word.chars() {
+        let (_, c) = chars.next()?;
+
+        if !case_insensitive_eq(c, expected) {
+            return None;
+        }
+    }
+
+    return Some(chars.next().map_or(src.len(), |(i, _)| return pos + i));
+}
+
+/// Get whether `c` is equal to the lowercase ASCII character `expected`, using the same (simple) case folding as the regex
+fn case_insensitive_eq(c: char, expected: char) -> bool {
+    return c.to_ascii_lowercase() == expected
+        // "LATIN SMALL LETTER LONG S" and "KELVIN SIGN" fold to ASCII characters
+        || (expected == 's' && c == '\u{17F}')
+        || (expected == 'k' && c == '\u{212A}');
+}
+
+/// Get whether `c` can start a filter name (`[A-Za-z_]`, case-insensitive)
+fn is_ident_start(c: char) -> bool {
+    return c.is_ascii_alphabetic() || c == '_' || c == '\u{17F}' || c == '\u{212A}';
+}
+
+/// Skip all whitespace starting at `pos`, returning the position of the first non-whitespace character
+fn skip_ws(src: &str, pos: usize) -> usize {
+    return src[pos..]
+        .find(|c: char| return !c.is_whitespace())
+        .map_or(src.len(), |i| return pos + i);
+}
+
+/// Skip all whitespace starting at `pos`, but require at least one whitespace character
+fn skip_ws1(src: &str, pos: usize) -> Option<usize> {
+    let end = skip_ws(src, pos);
+
+    if end == pos {
+        return None;
+    }
+
+    return Some(end);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use lazy_static::lazy_static;
+    use proptest::prelude::*;
+    use regex::Regex;
+
+    lazy_static! {
+        /// The regex the scanner has to match exactly like without format specs
+        static ref REFERENCE: Regex = Regex::new(concat!(
+            r"(?mi)(?P<escape>\{\{|\}\})",
+            r"|\{\s*#if\s+(?P<if>\S+?)\s*\}",
+            r#"|\{\s*#each\s+(?P<each>\S+?)(?:\s+sep\s*=\s*"(?P<sep>[^"]*)")?\s*\}"#,
+            r"|\{\s*(?P<else>#else)\s*\}",
+            r"|\{\s*(?P<end_if>/if)\s*\}",
+            r"|\{\s*(?P<end_each>/each)\s*\}",
+            r"|\{\s*(?P<name>\S+?)(?P<filters>(?:\s*\|\s*[A-Za-z_][A-Za-z0-9_]*(?:\([^\)\}]*\))?)*)\s*(?::-\s*(?P<default>[^\}]*?))?\s*\}",
+            r"|\{(?P<implicit>\s*)\}",
+        ))
+        .unwrap();
+
+        /// The regex the scanner has to match exactly like with format specs
+        static ref REFERENCE_SPECS: Regex = Regex::new(concat!(
+            r"(?mi)(?P<escape>\{\{|\}\})",
+            r"|\{\s*#if\s+(?P<if>\S+?)\s*\}",
+            r#"|\{\s*#each\s+(?P<each>\S+?)(?:\s+sep\s*=\s*"(?P<sep>[^"]*)")?\s*\}"#,
+            r"|\{\s*(?P<else>#else)\s*\}",
+            r"|\{\s*(?P<end_if>/if)\s*\}",
+            r"|\{\s*(?P<end_each>/each)\s*\}",
+            r"|\{\s*(?P<name>\S+?)(?P<filters>(?:\s*\|\s*[A-Za-z_][A-Za-z0-9_]*(?:\([^\)\}]*\))?)*)(?::(?P<spec>(?:[^\s\}\-]?[<>^])?\+?#?0?[0-9]*(?:\.[0-9]+)?(?-i:[xXobeE])?))?\s*(?::-\s*(?P<default>[^\}]*?))?\s*\}",
+            r"|\{(?P<implicit>\s*)\}",
+        ))
+        .unwrap();
+    }
+
+    /// Find all matches with [`REFERENCE`], or [`REFERENCE_SPECS`] if `format_specs` is set
+    fn reference_scan(template: &str, format_specs: bool) -> Vec<MatchEntry> {
+        let mut implicit = 0;
+        let reference: &Regex = if format_specs { &REFERENCE_SPECS } else { &REFERENCE };
+
+        return reference
+            .captures_iter(template)
+            .map(|found| {
+                let full_match = found.get(0).expect("Match Index 0 was None (Full Match)");
+                let full = (full_match.start(), full_match.end());
+
+                if let Some(escape) = found.name("escape") {
+                    return MatchEntry::new_escape(full, (escape.start(), escape.start() + 1));
+                }
+
+                if let Some(inner) = found.name("implicit") {
+                    implicit += 1;
+
+                    return MatchEntry::new_kind(MatchKind::Implicit(implicit - 1), full, (inner.start(), inner.end()));
+                }
+
+                for (group, kind) in &[
+                    ("if", MatchKind::If),
+                    ("each", MatchKind::Each),
+                    ("else", MatchKind::Else),
+                    ("end_if", MatchKind::EndIf),
+                    ("end_each", MatchKind::EndEach),
+                ] {
+                    if let Some(section) = found.name(group) {
+                        return MatchEntry::new_kind(*kind, full, (section.start(), section.end()))
+                            .with_separator(found.name("sep").map(|v| return (v.start(), v.end())));
+                    }
+                }
+
+                let value_match = found.name("name").expect("Match Group \"name\" was None (Inner Match)");
+                let filters = found.name("filters").expect("Match Group \"filters\" was None");
+                let format = found
+                    .name("spec")
+                    .filter(|v| return !v.as_str().is_empty())
+                    .map(|v| return FormatSpec::parse(v.as_str()));
+
+                return MatchEntry::new(full, (value_match.start(), value_match.end()))
+                    .with_default(found.name("default").map(|v| return (v.start(), v.end())))
+                    .with_filters(parse_filters(filters.as_str(), filters.start()))
+                    .with_format(format);
+            })
+            .collect();
+    }
+
+    /// Strategy for Template Strings built from parts of the syntax, which are likely to produce interesting matches
+    fn template_parts() -> impl Strategy<Value = String> {
+        let parts = prop::sample::select(vec![
+            "{",
+            "}",
+            "{{",
+            "}}",
+            " ",
+            "\n",
+            "\u{3000}",
+            "#if",
+            "#IF",
+            "#each",
+            "#eAcH",
+            "#else",
+            "#el\u{17F}e",
+            "/if",
+            "/each",
+            "sep",
+            "\u{17F}EP",
+            "=",
+            "\"",
+            "|",
+            "upper",
+            "tr\u{212A}",
+            "(",
+            ")",
+            ",",
+            ":-",
+            ":",
+            "-",
+            ">",
+            "<",
+            "^",
+            "+",
+            "#",
+            "0",
+            ".",
+            "x",
+            "E",
+            "o",
+            "5",
+            "a",
+            "b1",
+            "_",
+            "ä",
+            "10",
+        ]);
+
+        return prop::collection::vec(parts, 0..40).prop_map(|v| return v.concat());
+    }
+
+    proptest! {
+        #[test]
+        fn test_scan_like_regex_parts(template in template_parts(), format_specs in any::<bool>()) {
+            prop_assert_eq!(reference_scan(&template, format_specs), scan(&template, format_specs));
+        }
+
+        #[test]
+        fn test_scan_like_regex_chars(
+            template in "[{}#|():=\"\\- \ta-fs/_0-9äſ\u{212A}<>^+.xXoO]{0,40}",
+            format_specs in any::<bool>(),
+        ) {
+            prop_assert_eq!(reference_scan(&template, format_specs), scan(&template, format_specs));
+        }
+    }
+
+    #[test]
+    fn test_scan_like_regex() {
+        for template in &[
+            "Something {data1} be {data2}, and { not here }",
+            "{{data1}} {{{data1}}} {a}b} {a:b} {#iffy} {#if} {#if }} {#IF a}{/IF}",
+            "{#each list sep=\", \"}{.}{#else}none{/each} {#each list  SEP = \"x\" } {#each a sep=\"}",
+            "{data1 | upper | truncate( 10 ) | pad_left(}) :- some default }",
+            "{ data1:-} {data1 |} {data1| 1a} {data1:- a b }",
+            "{} { } {0} {}} { }} {{}} {\n}",
+            "{a:>10} {a:*^+#08.2x} {a:.} {a:} {a::>5} {a:-5} {a:>-5} {a:<<} {a | upper:5 :- x} {a:5 x} {a:O}",
+        ] {
+            for format_specs in &[false, true] {
+                assert_eq!(
+                    reference_scan(template, *format_specs),
+                    scan(template, *format_specs),
+                    "Template: {:?}, format specs: {}",
+                    template,
+                    format_specs
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,452 @@
+//! Module for converting any [`Serialize`] value into a [`Value`], only available with the `serde` feature
+
+use std::{
+    collections::BTreeMap,
+    error,
+    fmt,
+};
+
+use serde::ser::{
+    self,
+    Serialize,
+};
+
+use crate::value::{
+    from_wide_integer,
+    Value,
+};
+
+/// Error for a value that could not be serialized into a [`Value`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SerializeError(String);
+
+impl fmt::Display for SerializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return f.write_str(&self.0);
+    }
+}
+
+impl error::Error for SerializeError {}
+
+impl ser::Error for SerializeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        return Self(msg.to_string());
+    }
+}
+
+/// Serialize `value` into a [`Value`]
+pub(crate) fn to_value<T: Serialize + ?Sized>(value: &T) -> Result<Value, SerializeError> {
+    return value.serialize(ValueSerializer);
+}
+
+/// Create a [`Value::Map`] with the single entry `variant`, which is how enum variants with data are represented
+fn variant(variant: &str, value: Value) -> Value {
+    let mut map = BTreeMap::new();
+    map.insert(variant.to_string(), value);
+
+    return Value::Map(map);
+}
+
+/// The [`ser::Serializer`] that produces a [`Value`]
+struct ValueSerializer;
+
+impl ser::Serializer for ValueSerializer {
+    type Ok = Value;
+    type Error = SerializeError;
+
+    type SerializeSeq = SerializeList;
+    type SerializeTuple = SerializeList;
+    type SerializeTupleStruct = SerializeList;
+    type SerializeTupleVariant = SerializeList;
+    type SerializeMap = SerializeMap;
+    type SerializeStruct = SerializeMap;
+    type SerializeStructVariant = SerializeMap;
+
+    fn serialize_bool(self, v: bool) -> Result<Value, SerializeError> {
+        return Ok(Value::Bool(v));
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Value, SerializeError> {
+        return Ok(Value::Integer(v.into()));
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Value, SerializeError> {
+        return Ok(Value::Integer(v.into()));
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Value, SerializeError> {
+        return Ok(Value::Integer(v.into()));
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Value, SerializeError> {
+        return Ok(Value::Integer(v));
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Value, SerializeError> {
+        return Ok(from_wide_integer(v));
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Value, SerializeError> {
+        return Ok(Value::Integer(v.into()));
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Value, SerializeError> {
+        return Ok(Value::Integer(v.into()));
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Value, SerializeError> {
+        return Ok(Value::Integer(v.into()));
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Value, SerializeError> {
+        return Ok(from_wide_integer(v));
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Value, SerializeError> {
+        return Ok(from_wide_integer(v));
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Value, SerializeError> {
+        return Ok(Value::Float(v.into()));
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Value, SerializeError> {
+        return Ok(Value::Float(v));
+    }
+
+    fn serialize_char(self, v: char) -> Result<Value, SerializeError> {
+        return Ok(Value::String(v.to_string()));
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Value, SerializeError> {
+        return Ok(Value::String(v.to_string()));
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Value, SerializeError> {
+        return Ok(Value::List(
+            v.iter().map(|b| return Value::Integer((*b).into())).collect(),
+        ));
+    }
+
+    fn serialize_none(self) -> Result<Value, SerializeError> {
+        return Ok(Value::Null);
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Value, SerializeError> {
+        return value.serialize(self);
+    }
+
+    fn serialize_unit(self) -> Result<Value, SerializeError> {
+        return Ok(Value::Null);
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Value, SerializeError> {
+        return Ok(Value::Null);
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Value, SerializeError> {
+        return Ok(Value::String(variant.to_string()));
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Value, SerializeError> {
+        return value.serialize(self);
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant_name: &'static str,
+        value: &T,
+    ) -> Result<Value, SerializeError> {
+        return Ok(variant(variant_name, to_value(value)?));
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeList, SerializeError> {
+        return Ok(SerializeList {
+            variant: None,
+            items:   Vec::with_capacity(len.unwrap_or(0)),
+        });
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeList, SerializeError> {
+        return self.serialize_seq(Some(len));
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<SerializeList, SerializeError> {
+        return self.serialize_seq(Some(len));
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeList, SerializeError> {
+        return Ok(SerializeList {
+            variant: Some(variant),
+            items:   Vec::with_capacity(len),
+        });
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<SerializeMap, SerializeError> {
+        return Ok(SerializeMap {
+            variant: None,
+            entries: BTreeMap::new(),
+            key:     None,
+        });
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<SerializeMap, SerializeError> {
+        return self.serialize_map(Some(len));
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<SerializeMap, SerializeError> {
+        return Ok(SerializeMap {
+            variant: Some(variant),
+            entries: BTreeMap::new(),
+            key:     None,
+        });
+    }
+}
+
+/// Serializer for sequences, tuples and tuple variants, which all become a [`Value::List`]
+struct SerializeList {
+    /// The variant name, if this is a tuple variant
+    variant: Option<&'static str>,
+    items:   Vec<Value>,
+}
+
+impl SerializeList {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        self.items.push(to_value(value)?);
+
+        return Ok(());
+    }
+
+    fn finish(self) -> Result<Value, SerializeError> {
+        let list = Value::List(self.items);
+
+        return Ok(match self.variant {
+            Some(name) => variant(name, list),
+            None => list,
+        });
+    }
+}
+
+impl ser::SerializeSeq for SerializeList {
+    type Ok = Value;
+    type Error = SerializeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        return self.push(value);
+    }
+
+    fn end(self) -> Result<Value, SerializeError> {
+        return self.finish();
+    }
+}
+
+impl ser::SerializeTuple for SerializeList {
+    type Ok = Value;
+    type Error = SerializeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        return self.push(value);
+    }
+
+    fn end(self) -> Result<Value, SerializeError> {
+        return self.finish();
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeList {
+    type Ok = Value;
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        return self.push(value);
+    }
+
+    fn end(self) -> Result<Value, SerializeError> {
+        return self.finish();
+    }
+}
+
+impl ser::SerializeTupleVariant for SerializeList {
+    type Ok = Value;
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        return self.push(value);
+    }
+
+    fn end(self) -> Result<Value, SerializeError> {
+        return self.finish();
+    }
+}
+
+/// Serializer for maps, structs and struct variants, which all become a [`Value::Map`]
+struct SerializeMap {
+    /// The variant name, if this is a struct variant
+    variant: Option<&'static str>,
+    entries: BTreeMap<String, Value>,
+    /// The key given to [`ser::SerializeMap::serialize_key`], waiting for its value
+    key:     Option<String>,
+}
+
+impl SerializeMap {
+    fn insert<T: Serialize + ?Sized>(&mut self, key: String, value: &T) -> Result<(), SerializeError> {
+        self.entries.insert(key, to_value(value)?);
+
+        return Ok(());
+    }
+
+    fn finish(self) -> Result<Value, SerializeError> {
+        let map = Value::Map(self.entries);
+
+        return Ok(match self.variant {
+            Some(name) => variant(name, map),
+            None => map,
+        });
+    }
+}
+
+impl ser::SerializeMap for SerializeMap {
+    type Ok = Value;
+    type Error = SerializeError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), SerializeError> {
+        let key = match to_value(key)? {
+            Value::String(v) => v,
+            Value::Integer(v) => v.to_string(),
+            Value::Bool(v) => v.to_string(),
+            _ => {
+                return Err(SerializeError(
+                    "Map keys must be strings, integers or bools".to_string(),
+                ))
+            },
+        };
+        self.key = Some(key);
+
+        return Ok(());
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| return SerializeError("Map value serialized before its key".to_string()))?;
+
+        return self.insert(key, value);
+    }
+
+    fn end(self) -> Result<Value, SerializeError> {
+        return self.finish();
+    }
+}
+
+impl ser::SerializeStruct for SerializeMap {
+    type Ok = Value;
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), SerializeError> {
+        return self.insert(key.to_string(), value);
+    }
+
+    fn end(self) -> Result<Value, SerializeError> {
+        return self.finish();
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeMap {
+    type Ok = Value;
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), SerializeError> {
+        return self.insert(key.to_string(), value);
+    }
+
+    fn end(self) -> Result<Value, SerializeError> {
+        return self.finish();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde::Serialize;
+
+    #[derive(Serialize)]
+    enum Kind {
+        Unit,
+        Newtype(u8),
+        Tuple(u8, u8),
+        Struct { a: u8 },
+    }
+
+    #[test]
+    fn test_to_value_scalars() {
+        assert_eq!(Ok(Value::Bool(true)), to_value(&true));
+        assert_eq!(Ok(Value::Integer(-5)), to_value(&-5i8));
+        assert_eq!(Ok(Value::Integer(5)), to_value(&5u64));
+        assert_eq!(Ok(Value::String(u64::MAX.to_string())), to_value(&u64::MAX));
+        assert_eq!(Ok(Value::Float(1.5)), to_value(&1.5f32));
+        assert_eq!(Ok(Value::String("c".to_string())), to_value(&'c'));
+        assert_eq!(Ok(Value::Null), to_value(&None::<u8>));
+        assert_eq!(Ok(Value::Integer(1)), to_value(&Some(1u8)));
+        assert_eq!(Ok(Value::Null), to_value(&()));
+    }
+
+    #[test]
+    fn test_to_value_compound() {
+        assert_eq!(
+            Ok(Value::List(vec![Value::Integer(1), Value::String("a".to_string())])),
+            to_value(&(1, "a"))
+        );
+
+        let mut map = BTreeMap::new();
+        map.insert(1, "a");
+        assert_eq!(Ok(Value::from_pairs(vec![("1", "a")])), to_value(&map));
+
+        let mut map = BTreeMap::new();
+        map.insert((1, 2), "a");
+        assert!(to_value(&map).is_err());
+
+        assert_eq!(Ok(Value::String("Unit".to_string())), to_value(&Kind::Unit));
+        assert_eq!(
+            Ok(Value::from_pairs(vec![("Newtype", Value::Integer(1))])),
+            to_value(&Kind::Newtype(1))
+        );
+        assert_eq!(
+            Ok(Value::from_pairs(vec![(
+                "Tuple",
+                Value::List(vec![Value::Integer(1), Value::Integer(2)])
+            )])),
+            to_value(&Kind::Tuple(1, 2))
+        );
+        assert_eq!(
+            Ok(Value::from_pairs(vec![(
+                "Struct",
+                Value::from_pairs(vec![("a", Value::Integer(1))])
+            )])),
+            to_value(&Kind::Struct { a: 1 })
+        );
+    }
+}
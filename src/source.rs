@@ -0,0 +1,614 @@
+//! Module for [`ValueSource`], the lookup used by [`Template`](crate::template::Template) to resolve values
+
+use std::{
+    borrow::{
+        Borrow,
+        Cow,
+    },
+    collections::{
+        BTreeMap,
+        HashMap,
+    },
+    fmt::Display,
+    hash::{
+        BuildHasher,
+        Hash,
+    },
+    usize,
+};
+
+use crate::value::Number;
+
+/// Trait for everything that can provide values for the placeholders in a [`Template`](crate::template::Template)
+///
+/// Implementations are provided for:
+/// - [`HashMap`] and [`BTreeMap`] with keys that can be borrowed as [`str`] and values that are [`AsRef<str>`]
+/// - slices and [`Vec`]s of key-value pairs, where the first matching key is used
+/// - closures of the form `Fn(&str) -> Option<V>` where `V` is [`Display`]
+/// - `&dyn ValueSource`
+/// - [`Value`](crate::value::Value), which also provides lists for `{#each}` sections and nested values for paths
+/// - [`HashMap`] with keys that can be borrowed as [`str`] and [`Value`](crate::value::Value)s as values
+///
+/// Placeholders can use paths like `{user.address.city}` or `{jobs[2].name}`, which are resolved with
+/// [`ValueSource::get_child`] and [`ValueSource::get_list`] if there is no value for the full name.
+///
+/// # Example
+/// ```rust
+/// # use new_string_template::template::Template;
+/// # use new_string_template::source::ValueSource;
+/// # use std::borrow::Cow;
+/// struct Config {
+///     name: String,
+/// }
+///
+/// impl ValueSource for Config {
+///     fn get_value(&self, name: &str) -> Option<Cow<'_, str>> {
+///         return match name {
+///             "name" => Some(Cow::Borrowed(&self.name)),
+///             _ => None,
+///         };
+///     }
+/// }
+///
+/// let templ = Template::new("Hello {name}");
+/// let config = Config { name: "World".into() };
+/// assert_eq!("Hello World", templ.render_with(&config).expect("Expected Result to be Ok"));
+/// ```
+pub trait ValueSource {
+    /// Get the value for the placeholder `name`, or [`None`] if there is no value for it
+    fn get_value(&self, name: &str) -> Option<Cow<'_, str>>;
+
+    /// Get the list for the section `{#each name}`, where every item provides the values for one repetition of the section
+    ///
+    /// The default implementation provides no lists.
+    fn get_list(&self, _name: &str) -> Option<Vec<&dyn ValueSource>> {
+        return None;
+    }
+
+    /// Get the nested source `name`, which is used to resolve paths like `{user.address.city}`
+    ///
+    /// The default implementation provides no nested sources.
+    fn get_child(&self, _name: &str) -> Option<&dyn ValueSource> {
+        return None;
+    }
+
+    /// Get the value for the placeholder `name` as a typed number, which is required for numeric format specs like `{price:.2}` or `{id:08x}`
+    ///
+    /// The default implementation provides no numbers.
+    fn get_number(&self, _name: &str) -> Option<Number> {
+        return None;
+    }
+
+    /// Get the value `name` as a typed boolean, which is used for the condition of `{#if name}` instead of the value
+    ///
+    /// The default implementation provides no booleans, so conditions are true if the value is not empty.
+    fn get_bool(&self, _name: &str) -> Option<bool> {
+        return None;
+    }
+
+    /// Get the value for the positional placeholder `{}` at `index` (the first `{}` is index 0)
+    ///
+    /// The default implementation provides no positional values, so `{}` is rendered as-is.
+    fn get_index(&self, _index: usize) -> Option<Cow<'_, str>> {
+        return None;
+    }
+}
+
+impl<K, V, S> ValueSource for HashMap<K, V, S>
+where
+    K: Borrow<str> + Eq + Hash,
+    V: AsRef<str>,
+    S: BuildHasher,
+{
+    fn get_value(&self, name: &str) -> Option<Cow<'_, str>> {
+        return self.get(name).map(|v| return Cow::Borrowed(v.as_ref()));
+    }
+}
+
+impl<K, V> ValueSource for BTreeMap<K, V>
+where
+    K: Borrow<str> + Ord,
+    V: AsRef<str>,
+{
+    fn get_value(&self, name: &str) -> Option<Cow<'_, str>> {
+        return self.get(name).map(|v| return Cow::Borrowed(v.as_ref()));
+    }
+}
+
+impl<K, V> ValueSource for [(K, V)]
+where
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    fn get_value(&self, name: &str) -> Option<Cow<'_, str>> {
+        return self
+            .iter()
+            .find(|(k, _)| return k.as_ref() == name)
+            .map(|(_, v)| return Cow::Borrowed(v.as_ref()));
+    }
+}
+
+impl<K, V> ValueSource for Vec<(K, V)>
+where
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    fn get_value(&self, name: &str) -> Option<Cow<'_, str>> {
+        return self.as_slice().get_value(name);
+    }
+}
+
+impl<F, V> ValueSource for F
+where
+    F: Fn(&str) -> Option<V>,
+    V: Display,
+{
+    fn get_value(&self, name: &str) -> Option<Cow<'_, str>> {
+        return self(name).map(|v| return Cow::Owned(v.to_string()));
+    }
+}
+
+impl ValueSource for &dyn ValueSource {
+    fn get_value(&self, name: &str) -> Option<Cow<'_, str>> {
+        return (**self).get_value(name);
+    }
+
+    fn get_list(&self, name: &str) -> Option<Vec<&dyn ValueSource>> {
+        return (**self).get_list(name);
+    }
+
+    fn get_child(&self, name: &str) -> Option<&dyn ValueSource> {
+        return (**self).get_child(name);
+    }
+
+    fn get_number(&self, name: &str) -> Option<Number> {
+        return (**self).get_number(name);
+    }
+
+    fn get_bool(&self, name: &str) -> Option<bool> {
+        return (**self).get_bool(name);
+    }
+
+    fn get_index(&self, index: usize) -> Option<Cow<'_, str>> {
+        return (**self).get_index(index);
+    }
+}
+
+/// Wrapper to use a (possibly unsized) [`ValueSource`] as `&dyn ValueSource`
+pub(crate) struct SourceRef<'a, S: ValueSource + ?Sized>(pub(crate) &'a S);
+
+impl<S: ValueSource + ?Sized> ValueSource for SourceRef<'_, S> {
+    fn get_value(&self, name: &str) -> Option<Cow<'_, str>> {
+        return self.0.get_value(name);
+    }
+
+    fn get_list(&self, name: &str) -> Option<Vec<&dyn ValueSource>> {
+        return self.0.get_list(name);
+    }
+
+    fn get_child(&self, name: &str) -> Option<&dyn ValueSource> {
+        return self.0.get_child(name);
+    }
+
+    fn get_number(&self, name: &str) -> Option<Number> {
+        return self.0.get_number(name);
+    }
+
+    fn get_bool(&self, name: &str) -> Option<bool> {
+        return self.0.get_bool(name);
+    }
+
+    fn get_index(&self, index: usize) -> Option<Cow<'_, str>> {
+        return self.0.get_index(index);
+    }
+}
+
+/// The [`ValueSource`] for one item of a `{#each}` section, which falls back to the values outside of the section
+pub(crate) struct Scope<'a> {
+    /// The current item
+    pub(crate) inner: &'a dyn ValueSource,
+    /// The values outside of the section
+    pub(crate) outer: &'a dyn ValueSource,
+}
+
+impl ValueSource for Scope<'_> {
+    fn get_value(&self, name: &str) -> Option<Cow<'_, str>> {
+        return self.inner.get_value(name).or_else(|| return self.outer.get_value(name));
+    }
+
+    fn get_list(&self, name: &str) -> Option<Vec<&dyn ValueSource>> {
+        return self.inner.get_list(name).or_else(|| return self.outer.get_list(name));
+    }
+
+    fn get_child(&self, name: &str) -> Option<&dyn ValueSource> {
+        return self.inner.get_child(name).or_else(|| return self.outer.get_child(name));
+    }
+
+    fn get_number(&self, name: &str) -> Option<Number> {
+        return self
+            .inner
+            .get_number(name)
+            .or_else(|| return self.outer.get_number(name));
+    }
+
+    fn get_bool(&self, name: &str) -> Option<bool> {
+        return self.inner.get_bool(name).or_else(|| return self.outer.get_bool(name));
+    }
+
+    fn get_index(&self, index: usize) -> Option<Cow<'_, str>> {
+        return self
+            .inner
+            .get_index(index)
+            .or_else(|| return self.outer.get_index(index));
+    }
+}
+
+/// The [`ValueSource`] for positional placeholders, where `{}` and `{0}` are both resolved from a slice
+pub(crate) struct Positional<'a, T>(pub(crate) &'a [T]);
+
+/// Positional values are only [`Display`], so they are never typed numbers or booleans
+impl<T: Display> ValueSource for Positional<'_, T> {
+    fn get_value(&self, name: &str) -> Option<Cow<'_, str>> {
+        return explicit_index(name).and_then(|index| return self.get_index(index));
+    }
+
+    fn get_index(&self, index: usize) -> Option<Cow<'_, str>> {
+        return self.0.get(index).map(|v| return Cow::Owned(v.to_string()));
+    }
+}
+
+/// Get the index of a positional placeholder with index (like `{0}`), or [`None`] if `name` is not only ASCII digits
+///
+/// Indices that are too large are [`usize::MAX`], so they are always out of range.
+pub(crate) fn explicit_index(name: &str) -> Option<usize> {
+    if name.is_empty() || !name.bytes().all(|b| return b.is_ascii_digit()) {
+        return None;
+    }
+
+    return Some(name.parse().unwrap_or(usize::MAX));
+}
+
+/// A single segment of a path like `a.b[2].c`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Segment<'a> {
+    /// A named entry, like `b` in `a.b`
+    Key(&'a str),
+    /// A index into a list, like `2` in `b[2]`
+    Index(usize),
+}
+
+/// Parse `path` into its [`Segment`]s, each with the offset just after the segment in `path`
+///
+/// Returns [`None`] if `path` is not a valid path or just a single key.
+fn parse_path(path: &str) -> Option<Vec<(Segment<'_>, usize)>> {
+    let mut segments = Vec::new();
+    let mut offset = 0;
+
+    for part in path.split('.') {
+        let key_end = part.find('[').unwrap_or_else(|| return part.len());
+
+        if key_end == 0 {
+            return None;
+        }
+
+        offset += key_end;
+        segments.push((Segment::Key(&part[..key_end]), offset));
+
+        let mut rest = &part[key_end..];
+
+        while !rest.is_empty() {
+            let close = rest.find(']')?;
+            let index = rest[1..close].parse::<usize>().ok()?;
+            offset += close + 1;
+            segments.push((Segment::Index(index), offset));
+            rest = &rest[close + 1..];
+
+            if !rest.is_empty() && !rest.starts_with('[') {
+                return None;
+            }
+        }
+
+        offset += 1; // "+ 1" for the "." itself
+    }
+
+    if segments.len() < 2 {
+        return None;
+    }
+
+    return Some(segments);
+}
+
+/// The kind of a [`PathError`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PathErrorKind {
+    /// A segment of the path does not exist
+    Missing,
+    /// A segment of the path is used as a map or list, but is not one
+    NotAContainer,
+    /// The value is a map or list, but a single value is required
+    NotAScalar,
+}
+
+/// Error for a path that could not be resolved
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct PathError {
+    pub(crate) kind: PathErrorKind,
+    /// Offset just after the segment that failed, so that `&path[..end]` is the part of the path that failed
+    pub(crate) end:  usize,
+}
+
+/// A resolved (part of a) path
+enum Resolved<'a> {
+    Source(&'a dyn ValueSource),
+    List(Vec<&'a dyn ValueSource>),
+    Value(Cow<'a, str>),
+}
+
+/// Resolve the single segment `key` in `source`, `next` is the segment after it, if there is one
+fn resolve_key<'a, S: ValueSource + ?Sized>(
+    source: &'a S,
+    key: &str,
+    next: Option<Segment<'_>>,
+) -> Option<Resolved<'a>> {
+    // prefer a list if the next segment is a index
+    if let Some(Segment::Index(_)) = next {
+        if let Some(list) = source.get_list(key) {
+            return Some(Resolved::List(list));
+        }
+    }
+
+    if let Some(child) = source.get_child(key) {
+        return Some(Resolved::Source(child));
+    }
+
+    if let Some(list) = source.get_list(key) {
+        return Some(Resolved::List(list));
+    }
+
+    return source.get_value(key).map(Resolved::Value);
+}
+
+/// Resolve the path `path` in `source`, returning [`None`] if `path` is not a path
+fn resolve_path<'a, S: ValueSource + ?Sized>(source: &'a S, path: &str) -> Option<Result<Resolved<'a>, PathError>> {
+    let segments = parse_path(path)?;
+
+    return Some(resolve_segments(source, &segments, None));
+}
+
+/// Resolve all `segments` in `source`, `after` is the segment after the last one, if there is one
+fn resolve_segments<'a, S: ValueSource + ?Sized>(
+    source: &'a S,
+    segments: &[(Segment<'_>, usize)],
+    after: Option<Segment<'_>>,
+) -> Result<Resolved<'a>, PathError> {
+    let next_segment = |index: usize| return segments.get(index + 1).map(|v| return v.0).or(after);
+    let missing = |end| {
+        return PathError {
+            kind: PathErrorKind::Missing,
+            end,
+        };
+    };
+
+    // the first segment is always a key, and is resolved in "source" itself to keep its lifetime
+    let (first, first_end) = segments[0];
+    let first = match first {
+        Segment::Key(key) => key,
+        Segment::Index(_) => unreachable!("Expected the first segment to be a key"),
+    };
+    let mut current = match resolve_key(source, first, next_segment(0)) {
+        Some(v) => v,
+        None => return Err(missing(first_end)),
+    };
+    let mut prev_end = first_end;
+
+    for (index, (segment, end)) in segments.iter().enumerate().skip(1) {
+        let resolved = match (current, segment) {
+            (Resolved::Source(source), Segment::Key(key)) => resolve_key(source, key, next_segment(index)),
+            (Resolved::List(list), Segment::Index(i)) => list.get(*i).map(|v| return Resolved::Source(*v)),
+            _ => {
+                return Err(PathError {
+                    kind: PathErrorKind::NotAContainer,
+                    end:  prev_end,
+                });
+            },
+        };
+
+        current = match resolved {
+            Some(v) => v,
+            None => return Err(missing(*end)),
+        };
+        prev_end = *end;
+    }
+
+    return Ok(current);
+}
+
+/// Get the value `name` from `source`, resolving `name` as a path like `a.b[2].c` if there is no value for the full name
+pub(crate) fn get_value_path<'a, S: ValueSource + ?Sized>(
+    source: &'a S,
+    name: &str,
+) -> Result<Option<Cow<'a, str>>, PathError> {
+    if let Some(v) = source.get_value(name) {
+        return Ok(Some(v));
+    }
+
+    return resolve_value_path(source, name);
+}
+
+/// Resolve the value `name` from `source` as a path like `a.b[2].c`, for when there is no value for the full name
+pub(crate) fn resolve_value_path<'a, S: ValueSource + ?Sized>(
+    source: &'a S,
+    name: &str,
+) -> Result<Option<Cow<'a, str>>, PathError> {
+    let not_a_scalar = PathError {
+        kind: PathErrorKind::NotAScalar,
+        end:  name.len(),
+    };
+
+    return match resolve_path(source, name) {
+        None if source.get_child(name).is_some() || source.get_list(name).is_some() => Err(not_a_scalar),
+        None => Ok(None),
+        Some(Err(err)) => Err(err),
+        Some(Ok(Resolved::Value(v))) => Ok(Some(v)),
+        Some(Ok(Resolved::Source(v))) => v.get_value(".").map(Some).ok_or(not_a_scalar),
+        Some(Ok(Resolved::List(_))) => Err(not_a_scalar),
+    };
+}
+
+/// Get the number `name` from `source`, resolving `name` as a path like `a.b[2].c` if there is no number for the full name
+///
+/// Returns [`None`] if there is no number, errors in the path are reported by [`get_value_path`] instead.
+pub(crate) fn get_number_path<S: ValueSource + ?Sized>(source: &S, name: &str) -> Option<Number> {
+    return source
+        .get_number(name)
+        .or_else(|| return get_typed_path(source, name, |parent, key| return parent.get_number(key)));
+}
+
+/// Get the boolean `name` from `source`, resolving `name` as a path like `a.b[2].c` if there is no boolean for the full name
+///
+/// Returns [`None`] if there is no boolean, errors in the path are reported by [`get_value_path`] instead.
+pub(crate) fn get_bool_path<S: ValueSource + ?Sized>(source: &S, name: &str) -> Option<bool> {
+    return source
+        .get_bool(name)
+        .or_else(|| return get_typed_path(source, name, |parent, key| return parent.get_bool(key)));
+}
+
+/// Resolve the path `name` in `source` and get the last segment of it with `get` from its parent
+fn get_typed_path<S, T, F>(source: &S, name: &str, get: F) -> Option<T>
+where
+    S: ValueSource + ?Sized,
+    F: Fn(&dyn ValueSource, &str) -> Option<T>,
+{
+    let segments = parse_path(name)?;
+    let (parents, last) = segments.split_at(segments.len() - 1);
+
+    return match (resolve_segments(source, parents, Some(last[0].0)).ok()?, last[0].0) {
+        (Resolved::Source(parent), Segment::Key(key)) => get(parent, key),
+        (Resolved::List(list), Segment::Index(i)) => get(*list.get(i)?, "."),
+        _ => None,
+    };
+}
+
+/// Get the list `name` from `source`, resolving `name` as a path like `a.b[2].c` if there is no list for the full name
+pub(crate) fn get_list_path<'a, S: ValueSource + ?Sized>(
+    source: &'a S,
+    name: &str,
+) -> Result<Option<Vec<&'a dyn ValueSource>>, PathError> {
+    if let Some(v) = source.get_list(name) {
+        return Ok(Some(v));
+    }
+
+    return match resolve_path(source, name) {
+        None => Ok(None),
+        Some(Err(err)) => Err(err),
+        Some(Ok(Resolved::List(v))) => Ok(Some(v)),
+        Some(Ok(_)) => Ok(None),
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_hashmap_str_key() {
+        let mut map = HashMap::new();
+        map.insert("data1", "should");
+
+        assert_eq!(Some(Cow::Borrowed("should")), map.get_value("data1"));
+        assert_eq!(None, map.get_value("data2"));
+    }
+
+    #[test]
+    fn test_hashmap_string_key() {
+        let mut map = HashMap::new();
+        map.insert("data1".to_string(), "should".to_string());
+
+        assert_eq!(Some(Cow::Borrowed("should")), map.get_value("data1"));
+        assert_eq!(None, map.get_value("data2"));
+    }
+
+    #[test]
+    fn test_btreemap() {
+        let mut map = BTreeMap::new();
+        map.insert("data1".to_string(), "should");
+
+        assert_eq!(Some(Cow::Borrowed("should")), map.get_value("data1"));
+        assert_eq!(None, map.get_value("data2"));
+    }
+
+    #[test]
+    fn test_pairs_first_match() {
+        let pairs = vec![("data1", "first"), ("data1", "second")];
+
+        assert_eq!(Some(Cow::Borrowed("first")), pairs.get_value("data1"));
+        assert_eq!(Some(Cow::Borrowed("first")), pairs[..].get_value("data1"));
+        assert_eq!(None, pairs.get_value("data2"));
+    }
+
+    #[test]
+    fn test_closure() {
+        let source = |name: &str| {
+            return match name {
+                "number" => Some(10),
+                _ => None,
+            };
+        };
+
+        assert_eq!(Some(Cow::Owned::<str>("10".to_string())), source.get_value("number"));
+        assert_eq!(None, source.get_value("data2"));
+    }
+
+    #[test]
+    fn test_dyn() {
+        let mut map = HashMap::new();
+        map.insert("data1", "should");
+        let source: &dyn ValueSource = &map;
+
+        assert_eq!(Some(Cow::Borrowed("should")), source.get_value("data1"));
+        assert_eq!(None, (&source).get_value("data2"));
+    }
+
+    #[test]
+    fn test_scope() {
+        let inner = vec![("data1", "inner")];
+        let outer = [("data1", "outer"), ("data2", "outer")];
+        let scope = Scope {
+            inner: &inner,
+            outer: &SourceRef(&outer[..]),
+        };
+
+        assert_eq!(Some(Cow::Borrowed("inner")), scope.get_value("data1"));
+        assert_eq!(Some(Cow::Borrowed("outer")), scope.get_value("data2"));
+        assert_eq!(None, scope.get_value("data3"));
+        assert!(scope.get_list("data1").is_none());
+    }
+
+    #[test]
+    fn test_parse_path() {
+        assert_eq!(
+            Some(vec![
+                (Segment::Key("a"), 1),
+                (Segment::Key("b"), 3),
+                (Segment::Index(2), 6),
+                (Segment::Key("c"), 8),
+            ]),
+            parse_path("a.b[2].c")
+        );
+        assert_eq!(
+            Some(vec![
+                (Segment::Key("a"), 1),
+                (Segment::Index(0), 4),
+                (Segment::Index(1), 7)
+            ]),
+            parse_path("a[0][1]")
+        );
+        assert_eq!(None, parse_path("a"));
+        assert_eq!(None, parse_path("."));
+        assert_eq!(None, parse_path("a..b"));
+        assert_eq!(None, parse_path("a[x]"));
+        assert_eq!(None, parse_path("a[0"));
+        assert_eq!(None, parse_path("a[0]b"));
+        assert_eq!(None, parse_path("[0]"));
+    }
+}
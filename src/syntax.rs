@@ -0,0 +1,238 @@
+//! Module for [`Syntax`], custom delimiters for placeholders without writing a regex
+
+use crate::{
+    error::{
+        TemplateError,
+        TemplateErrorKind,
+    },
+    template::MatchEntry,
+};
+
+/// Custom placeholder syntax, used with [`Template::with_syntax`](crate::template::Template::with_syntax)
+///
+/// A placeholder is the open delimiter, the value name and the close delimiter, like `{{ name }}` or `${name}`.
+/// The close delimiter can be empty, then the value name is as long as possible and has to follow the open delimiter directly (like `#name`).
+///
+/// Like with a custom regex, escapes, sections, filters and default values are not supported.
+/// # Example
+/// ```rust
+/// # use new_string_template::template::Template;
+/// # use new_string_template::syntax::Syntax;
+/// let syntax = Syntax::new("<<", ">>").with_name_chars(|c| return c.is_ascii_alphanumeric() || c == '_');
+/// let templ = Template::new("Hello << name >>, <<not a name>>").with_syntax(&syntax);
+///
+/// let rendered = templ.render_with(&vec![("name", "World")]).expect("Expected Result to be Ok");
+/// assert_eq!("Hello World, <<not a name>>", rendered);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Syntax {
+    /// The delimiter before the value name
+    open:       String,
+    /// The delimiter after the value name, can be empty
+    close:      String,
+    /// Whether whitespace between the delimiters and the value name is allowed (and ignored)
+    trim:       bool,
+    /// The characters allowed in value names
+    name_chars: fn(char) -> bool,
+}
+
+impl Syntax {
+    /// Create a new [`Syntax`] with the delimiters `open` and `close`
+    ///
+    /// By default whitespace around the value name is ignored and all non-whitespace characters are allowed in value names.
+    /// # Panics
+    /// If `open` is empty, see [`Syntax::try_new`]
+    pub fn new<O: Into<String>, C: Into<String>>(open: O, close: C) -> Self {
+        return Self::try_new(open, close).expect("Expected the open delimiter to not be empty");
+    }
+
+    /// Create a new [`Syntax`] with the delimiters `open` and `close`, like [`Syntax::new`], but error on invalid delimiters
+    /// # Errors
+    /// A [`TemplateErrorKind::InvalidSyntax`] error is returned if `open` is empty
+    /// # Example
+    /// ```rust
+    /// # use new_string_template::syntax::Syntax;
+    /// # use new_string_template::error::TemplateErrorKind;
+    /// assert!(Syntax::try_new("${", "}").is_ok());
+    ///
+    /// let err = Syntax::try_new("", "}").expect_err("Expected Result to be Err");
+    /// assert_eq!(TemplateErrorKind::InvalidSyntax, err.kind());
+    /// ```
+    pub fn try_new<O: Into<String>, C: Into<String>>(open: O, close: C) -> Result<Self, TemplateError> {
+        let open = open.into();
+
+        if open.is_empty() {
+            return Err(TemplateError::new(
+                TemplateErrorKind::InvalidSyntax,
+                "The open delimiter cannot be empty",
+            ));
+        }
+
+        return Ok(Self {
+            open,
+            close: close.into(),
+            trim: true,
+            name_chars: |c| return !c.is_whitespace(),
+        });
+    }
+
+    /// Set whether whitespace between the delimiters and the value name is allowed (and ignored), like `{{ name }}`
+    #[must_use]
+    pub fn with_trim(mut self, trim: bool) -> Self {
+        self.trim = trim;
+
+        return self;
+    }
+
+    /// Set the characters allowed in value names
+    ///
+    /// Value names always end before the close delimiter, even if its characters are allowed.
+    #[must_use]
+    pub fn with_name_chars(mut self, name_chars: fn(char) -> bool) -> Self {
+        self.name_chars = name_chars;
+
+        return self;
+    }
+
+    /// Find all placeholders of this syntax in `template`, in order
+    pub(crate) fn scan(&self, template: &str) -> Vec<MatchEntry> {
+        let mut matches = Vec::new();
+        let mut pos = 0;
+
+        while let Some(offset) = template[pos..].find(self.open.as_str()) {
+            let start = pos + offset;
+
+            match self.scan_at(template, start) {
+                Some(entry) => {
+                    pos = entry.full_match_end;
+                    matches.push(entry);
+                },
+                None => pos = start + template[start..].chars().next().map_or(1, char::len_utf8),
+            }
+        }
+
+        return matches;
+    }
+
+    /// Try to match a placeholder at `start`, which is the start of the open delimiter
+    fn scan_at(&self, src: &str, start: usize) -> Option<MatchEntry> {
+        let name_start = if self.close.is_empty() {
+            start + self.open.len()
+        } else {
+            self.skip_ws(src, start + self.open.len())
+        };
+        let name_len = src[name_start..]
+            .char_indices()
+            .find(|(i, c)| {
+                return !(self.name_chars)(*c)
+                    || (!self.close.is_empty() && src[name_start + i..].starts_with(&self.close));
+            })
+            .map_or(src.len() - name_start, |(i, _)| return i);
+
+        if name_len == 0 {
+            return None;
+        }
+
+        let name_end = name_start + name_len;
+        let close_start = if self.close.is_empty() {
+            name_end
+        } else {
+            self.skip_ws(src, name_end)
+        };
+
+        if !src[close_start..].starts_with(&self.close) {
+            return None;
+        }
+
+        return Some(MatchEntry::new(
+            (start, close_start + self.close.len()),
+            (name_start, name_end),
+        ));
+    }
+
+    /// Skip all whitespace starting at `pos` if whitespace is allowed
+    fn skip_ws(&self, src: &str, pos: usize) -> usize {
+        if !self.trim {
+            return pos;
+        }
+
+        return src[pos..]
+            .find(|c: char| return !c.is_whitespace())
+            .map_or(src.len(), |i| return pos + i);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::template::Template;
+
+    /// Render `template` with `syntax`, replacing every placeholder with its value name in brackets
+    fn render(syntax: &Syntax, template: &str) -> String {
+        let values = |name: &str| return Some(format!("[{}]", name));
+
+        return Template::new_syntax(template, syntax)
+            .render_with(&values)
+            .expect("Expected Result to be Ok");
+    }
+
+    #[test]
+    fn test_delimiters() {
+        assert_eq!(
+            "a [x] [y] {{ }} {{z w}}",
+            render(&Syntax::new("{{", "}}"), "a {{ x }} {{y}} {{ }} {{z w}}")
+        );
+        assert_eq!("a [x] $y ${ }", render(&Syntax::new("${", "}"), "a ${x} $y ${ }"));
+        assert_eq!("[x] 100% [y]", render(&Syntax::new("%", "%"), "%x% 100% %y%"));
+        assert_eq!("[x] [<y]", render(&Syntax::new("<<", ">>"), "<<x>> <<<y>>"));
+        assert_eq!("[x] [y.z] # ä[w]", render(&Syntax::new("#", ""), "#x #y.z # ä#w"));
+    }
+
+    #[test]
+    fn test_trim_and_name_chars() {
+        assert_eq!(
+            "{{ x }} [y]",
+            render(&Syntax::new("{{", "}}").with_trim(false), "{{ x }} {{y}}")
+        );
+
+        let syntax = Syntax::new("#", "").with_name_chars(|c| return c.is_ascii_alphanumeric());
+        assert_eq!("[x].y #ä", render(&syntax, "#x.y #ä"));
+    }
+
+    #[test]
+    fn test_default_syntax_ignored() {
+        let templ = Template::new("{x} {{y}}").with_syntax(&Syntax::new("{{", "}}"));
+        assert_eq!(
+            vec!["y"],
+            templ.placeholders().map(|v| return v.name()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected the open delimiter to not be empty")]
+    fn test_empty_open() {
+        let _ = Syntax::new("", "}");
+    }
+
+    #[test]
+    fn test_try_new() {
+        let err = Syntax::try_new("", "}").expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::InvalidSyntax, err.kind());
+        assert_eq!("InvalidSyntax: The open delimiter cannot be empty", err.to_string());
+
+        let syntax = Syntax::try_new("${", "}").expect("Expected Result to be Ok");
+        let templ = Template::try_new_syntax("a ${x}", &syntax).expect("Expected Result to be Ok");
+        assert_eq!(
+            vec!["x"],
+            templ.placeholders().map(|v| return v.name()).collect::<Vec<_>>()
+        );
+
+        let templ = Template::new("{x} ${y}")
+            .try_with_syntax(&syntax)
+            .expect("Expected Result to be Ok");
+        assert_eq!(
+            vec!["y"],
+            templ.placeholders().map(|v| return v.name()).collect::<Vec<_>>()
+        );
+    }
+}
@@ -1,20 +1,70 @@
 //! Module to contain everything needed for [`Template`]
 
 use std::{
-    collections::HashMap,
+    borrow::{
+        Borrow,
+        Cow,
+    },
+    collections::{
+        BTreeSet,
+        HashMap,
+    },
+    fmt,
+    hash::Hash,
+    io,
+    sync::Arc,
     usize,
 };
 
-use crate::error::{
-    TemplateError,
-    TemplateErrorKind,
+use crate::{
+    error::{
+        Span,
+        TemplateError,
+        TemplateErrorKind,
+    },
+    filter::{
+        FilterArg,
+        FilterCall,
+        FilterRegistry,
+        FilterResult,
+    },
+    format::FormatSpec,
+    scanner::scan,
+    source::{
+        explicit_index,
+        get_bool_path,
+        get_list_path,
+        get_number_path,
+        get_value_path,
+        resolve_value_path,
+        PathError,
+        PathErrorKind,
+        Positional,
+        Scope,
+        SourceRef,
+        ValueSource,
+    },
+    syntax::Syntax,
+    value::{
+        Number,
+        Value,
+    },
 };
+#[cfg(feature = "custom-regex")]
+use crate::{
+    filter::parse_filters,
+    scanner::is_filter_chain,
+};
+#[cfg(feature = "custom-regex")]
 use lazy_static::lazy_static;
+#[cfg(feature = "custom-regex")]
 use regex::Regex;
 
+#[cfg(feature = "custom-regex")]
 lazy_static! {
-    /// The Default Regex Template
+    /// The Default Regex Template, only available with the `custom-regex` feature
     ///
+    /// This is the basic syntax without escapes, sections, filters and default values, [`Template::new`] does not use this regex anymore.
     /// This template requires a single opening and closing bracket, all whitespaces are ignored between bracket and first non-whitespace (same for ending bracket).
     ///
     /// Example:
@@ -28,21 +78,104 @@ lazy_static! {
     pub static ref DEFAULT_TEMPLATE: Regex = Regex::new(r"(?mi)\{\s*(\S+?)\s*\}").unwrap();
 }
 
+/// The kind of a [`MatchEntry`]
+#[derive(Debug, Clone, PartialEq, Copy)]
+pub(crate) enum MatchKind {
+    /// The match is a placeholder, which will be replaced by its value
+    Placeholder,
+    /// The match is an escape, which will be replaced by the "value name" part of the match as-is
+    Escape,
+    /// The match is the start of a conditional section (`{#if data1}`), the "value name" is the value to check
+    If,
+    /// The match is the start of the else branch of a section (`{#else}`)
+    Else,
+    /// The match is the end of a conditional section (`{/if}`)
+    EndIf,
+    /// The match is the start of a repeated section (`{#each list}`), the "value name" is the list to repeat for
+    Each,
+    /// The match is the end of a repeated section (`{/each}`)
+    EndEach,
+    /// The match is a positional placeholder without index (`{}`), with its position among all of them
+    Implicit(usize),
+}
+
+impl MatchKind {
+    /// Get the name of the section this kind starts or ends, used in error messages
+    fn section_name(self) -> &'static str {
+        return match self {
+            Self::Each | Self::EndEach => "each",
+            Self::If | Self::EndIf | Self::Else | Self::Placeholder | Self::Escape | Self::Implicit(_) => "if",
+        };
+    }
+}
+
+/// A Node in the tree of a parsed Template String, which is built from the [`MatchEntry`]s
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    /// Literal text of the Template String, which will be rendered as-is
+    Text { start: usize, end: usize },
+    /// A placeholder or escape, as index into the [`MatchEntry`]s
+    Match(usize),
+    /// A conditional section, where `condition` is the index of the `{#if}` into the [`MatchEntry`]s
+    If {
+        condition: usize,
+        then:      Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+    /// A repeated section, where `list` is the index of the `{#each}` into the [`MatchEntry`]s
+    /// `otherwise` is rendered if the list is empty
+    Each {
+        list:      usize,
+        body:      Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+}
+
+/// A section that is not closed yet while building the [`Node`] tree
+struct OpenSection {
+    /// Index of the `{#if}` or `{#each}` into the [`MatchEntry`]s
+    start:     usize,
+    then:      Vec<Node>,
+    /// The else branch, if a `{#else}` was already found
+    otherwise: Option<Vec<Node>>,
+}
+
+impl OpenSection {
+    /// Get the branch new nodes are currently added to
+    fn current(&mut self) -> &mut Vec<Node> {
+        return self.otherwise.as_mut().unwrap_or(&mut self.then);
+    }
+}
+
 /// This is a Struct to Help with caching matches found
 /// Stores the beginning and end of the full match and the beginning and end of the value name inside the full match
-#[derive(Debug, Clone, PartialEq, Copy)]
-struct MatchEntry {
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct MatchEntry {
     /// Offset for the beginning of the full match in a parsed Template String
-    full_match_start: usize,
+    full_match_start:          usize,
     /// Offset for the end of the full match in a parsed Template String
     /// This refers to the character just after the match (using [`regex::Match::end`])
-    full_match_end:   usize,
+    pub(crate) full_match_end: usize,
 
     /// Offset for the beginning of the value name match in a parsed Template String
     value_name_start: usize,
     /// Offset for the end of the value name match in a parsed Template String
     /// This refers to the character just after the match (using [`regex::Match::end`])
     value_name_end:   usize,
+
+    /// Offsets for the beginning and end of the default value in a parsed Template String, if there is one
+    /// The end refers to the character just after the match (using [`regex::Match::end`])
+    default_value: Option<(usize, usize)>,
+    /// All filters that will be applied to the value, in order
+    filters:       Vec<FilterCall>,
+    /// The format spec applied to the value after the filters, like `>10` in `{name:>10}`
+    format:        Option<FormatSpec>,
+    /// Offsets for the beginning and end of the separator of a `{#each}`, if there is one
+    /// The end refers to the character just after the match (using [`regex::Match::end`])
+    separator:     Option<(usize, usize)>,
+
+    /// What this match is, and so how it will be rendered
+    kind: MatchKind,
 }
 
 impl MatchEntry {
@@ -54,21 +187,163 @@ impl MatchEntry {
 
             value_name_start: value_name.0,
             value_name_end:   value_name.1,
+
+            default_value: None,
+            filters:       Vec::new(),
+            format:        None,
+            separator:     None,
+
+            kind: MatchKind::Placeholder,
+        };
+    }
+
+    /// Set the default value offsets for this [`MatchEntry`]
+    pub fn with_default(mut self, default_value: Option<(usize, usize)>) -> Self {
+        self.default_value = default_value;
+
+        return self;
+    }
+
+    /// Set the separator offsets for this [`MatchEntry`]
+    pub fn with_separator(mut self, separator: Option<(usize, usize)>) -> Self {
+        self.separator = separator;
+
+        return self;
+    }
+
+    /// Set the filters for this [`MatchEntry`]
+    pub fn with_filters(mut self, filters: Vec<FilterCall>) -> Self {
+        self.filters = filters;
+
+        return self;
+    }
+
+    /// Set the format spec for this [`MatchEntry`]
+    pub fn with_format(mut self, format: Option<FormatSpec>) -> Self {
+        self.format = format;
+
+        return self;
+    }
+
+    /// Create a new [`MatchEntry`] instance for an escape, where `literal` is the part of the match that will be rendered
+    pub fn new_escape(full_match: (usize, usize), literal: (usize, usize)) -> Self {
+        return Self::new_kind(MatchKind::Escape, full_match, literal);
+    }
+
+    /// Create a new [`MatchEntry`] instance of `kind`
+    pub fn new_kind(kind: MatchKind, full_match: (usize, usize), value_name: (usize, usize)) -> Self {
+        return Self {
+            kind,
+            ..Self::new(full_match, value_name)
+        };
+    }
+
+    /// Get whether this match is a section tag (like `{#if}`, `{#else}` or `{/if}`)
+    pub fn is_section(&self) -> bool {
+        return match self.kind {
+            MatchKind::If | MatchKind::Else | MatchKind::EndIf | MatchKind::Each | MatchKind::EndEach => true,
+            MatchKind::Placeholder | MatchKind::Escape | MatchKind::Implicit(_) => false,
+        };
+    }
+
+    /// Get whether this match is a placeholder
+    pub fn is_placeholder(&self) -> bool {
+        return self.kind == MatchKind::Placeholder;
+    }
+
+    /// Get whether this match is a positional placeholder without index (`{}`)
+    pub fn is_implicit(&self) -> bool {
+        return match self.kind {
+            MatchKind::Implicit(_) => true,
+            _ => false,
         };
     }
 }
 
+/// A placeholder found in a [`Template`], see [`Template::placeholders`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Placeholder<'a> {
+    /// Name of the value used for this placeholder
+    name:          &'a str,
+    /// Position of the full placeholder in the Template String
+    span:          Span,
+    /// How often a placeholder with the same name is in the Template String
+    count:         usize,
+    /// The default value used when no value is provided, if any
+    default_value: Option<&'a str>,
+    /// Whether the placeholder is inside a `{#each}` section
+    repeated:      bool,
+    /// Whether the placeholder is the condition of a `{#if}` or the list of a `{#each}` section
+    section:       bool,
+}
+
+impl<'a> Placeholder<'a> {
+    /// Get the name of the value used for this placeholder
+    #[must_use]
+    pub const fn name(&self) -> &'a str {
+        return self.name;
+    }
+
+    /// Get the [`Span`] of the full placeholder in the Template String
+    #[must_use]
+    pub const fn span(&self) -> Span {
+        return self.span;
+    }
+
+    /// Get how often a placeholder with the same name is in the Template String (including this one)
+    #[must_use]
+    pub const fn count(&self) -> usize {
+        return self.count;
+    }
+
+    /// Get the default value used when no value is provided for this placeholder, if any
+    #[must_use]
+    pub const fn default_value(&self) -> Option<&'a str> {
+        return self.default_value;
+    }
+
+    /// Get whether this placeholder is inside a `{#each}` section, where it is looked up in the items of the list first
+    #[must_use]
+    pub const fn is_repeated(&self) -> bool {
+        return self.repeated;
+    }
+
+    /// Get whether this placeholder is the condition of a `{#if}` or the list of a `{#each}` section instead of a rendered value
+    #[must_use]
+    pub const fn is_section(&self) -> bool {
+        return self.section;
+    }
+}
+
 /// Struct to store the template
+///
+/// With the `serde` feature, a Template can be serialized (as its Template String) and deserialized (using [`Template::try_new`]),
+/// custom regexes and filters are not part of the serialized form.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Template {
     /// Template String
-    src:     String,
+    src:         String,
     /// All matches from the Template String
-    matches: Vec<MatchEntry>,
+    matches:     Vec<MatchEntry>,
+    /// The tree of the Template String, built from "matches"
+    nodes:       Vec<Node>,
+    /// The total length of all literal text, [`None`] if there are sections (then the rendered text depends on the values)
+    literal_len: Option<usize>,
+    /// Custom filters added with [`Template::register_filter`]
+    filters:     FilterRegistry,
 }
 
 impl Template {
-    /// Create a new Template Instance with the default regex.
+    /// Create a new Template Instance with the default syntax.
+    ///
+    /// The default syntax is `{data1}`, spaces around the value name are ignored (like `{ data1 }`).  
+    /// The default syntax supports escapes, so `{{` and `}}` will be rendered as a literal `{` and `}`.  
+    /// The default syntax also supports default values, so `{data1:-default}` will be rendered as `default` if there is no value for `data1`.  
+    /// The default syntax also supports filters, so `{data1 | upper}` will be rendered as the uppercase value of `data1`, see [`Template::register_filter`].  
+    /// The default syntax also supports `format!`-style specs after the filters (like `{data1:>10}`) if enabled, see [`Template::with_format_specs`].  
+    /// The default syntax also supports conditional and repeated sections, see [`Template::try_new`].
+    ///
+    /// If the sections are invalid (like a `{/if}` without `{#if}`), all section tags are treated as normal text, use [`Template::try_new`] to get a error instead.
     /// # Example
     /// ```rust
     /// # use new_string_template::template::*;
@@ -77,15 +352,95 @@ impl Template {
     /// ```
     pub fn new<T: Into<String>>(template: T) -> Self {
         let converted_string = template.into();
-        let matches = get_matches(&DEFAULT_TEMPLATE, &converted_string);
+        let (matches, nodes) = scan_lenient(&converted_string, false);
+
         return Self {
             src: converted_string,
             matches,
+            literal_len: literal_len(&nodes),
+            nodes,
+            filters: FilterRegistry::default(),
         };
     }
 
-    /// Change the [`Regex`] that is used to resolve the matches from the template string.  
+    /// Enable `format!`-style specs after the filters of placeholders, like `{data1:>10}`, `{price:.2}` or `{id:08x}`.
+    ///
+    /// Format specs are not enabled by default, because value names can contain a `:`: without format specs `{a:b}` is the value name `a:b`,
+    /// with format specs it is the value name `a` with the spec `b` (and `{port:8080}` the value name `port` with the width 8080).  
+    /// Numeric specs (with a sign, `#`, `0`, a precision or a type) require the value source to provide a typed number (like [`Value`] does),
+    /// otherwise a [`TemplateErrorKind::NotANumber`] error is returned.
+    ///
+    /// This parses the Template String again with the default syntax (like [`Template::new`]), so it replaces a custom [`Syntax`] or regex.
+    /// # Example
+    /// ```rust
+    /// # use new_string_template::template::*;
+    /// # use new_string_template::value::Value;
+    /// let data = Value::from_pairs(vec![("name", Value::from("abc")), ("price", Value::from(4.5))]);
+    ///
+    /// let templ = Template::new("{name:>5}: {price:.2}").with_format_specs();
+    /// assert_eq!("  abc: 4.50", templ.render_with(&data).expect("Expected Result to be Ok"));
+    ///
+    /// // without format specs, the ":" is part of the value name
+    /// let templ = Template::new("{name:>5}");
+    /// assert_eq!(vec!["name:>5"], templ.placeholders().map(|v| return v.name()).collect::<Vec<_>>());
+    /// ```
+    #[must_use]
+    pub fn with_format_specs(mut self) -> Self {
+        let (matches, nodes) = scan_lenient(&self.src, true);
+        self.matches = matches;
+        self.literal_len = literal_len(&nodes);
+        self.nodes = nodes;
+
+        return self;
+    }
+
+    /// Create a new Template Instance with the default syntax, like [`Template::new`], but error on invalid syntax.
+    ///
+    /// Conditional sections render their content only if the value is provided and not empty, with an optional `{#else}` branch:
+    /// `{#if data1}...{#else}...{/if}`, sections can be nested.  
+    /// Typed booleans (see [`ValueSource::get_bool`]) are used as-is, lists are true if they are not empty and nested sources (like maps) are always true.
+    ///
+    /// Repeated sections render their content once for every item of a list (see [`ValueSource::get_list`]),
+    /// with an optional separator between the items and an optional `{#else}` branch for empty lists:
+    /// `{#each list sep=", "}...{#else}...{/each}`.  
+    /// Inside a repeated section, values are looked up in the current item first and then outside of the section.  
+    /// Rendering a repeated section errors with [`TemplateErrorKind::MissingData`] if the list is missing and with [`TemplateErrorKind::NotAList`]
+    /// if the value is not a list, the `render_nofail` functions render the `{#else}` branch instead.
+    /// # Errors
+    /// A [`TemplateErrorKind::InvalidSyntax`] error is returned if the sections are invalid, like a `{/if}` without `{#if}`,
+    /// a `{#else}` outside of a section or a `{#if}` that is never closed.
+    /// # Example
+    /// ```rust
+    /// # use new_string_template::template::*;
+    /// let templ = Template::try_new("Mail to {to}{#if cc}, cc: {cc}{/if}").expect("Expected Result to be Ok");
+    ///
+    /// let rendered = templ.render_with(&vec![("to", "someone")]).expect("Expected Result to be Ok");
+    /// assert_eq!("Mail to someone", rendered);
+    /// let rendered = templ
+    ///     .render_with(&vec![("to", "someone"), ("cc", "other")])
+    ///     .expect("Expected Result to be Ok");
+    /// assert_eq!("Mail to someone, cc: other", rendered);
+    ///
+    /// assert!(Template::try_new("{#if data1} unclosed").is_err());
+    /// ```
+    pub fn try_new<T: Into<String>>(template: T) -> Result<Self, TemplateError> {
+        let converted_string = template.into();
+        let matches = scan(&converted_string, false);
+        let nodes = build_tree(&converted_string, &matches)?;
+
+        return Ok(Self {
+            src: converted_string,
+            matches,
+            literal_len: literal_len(&nodes),
+            nodes,
+            filters: FilterRegistry::default(),
+        });
+    }
+
+    /// Change the [`Regex`] that is used to resolve the matches from the template string, only available with the `custom-regex` feature.  
     /// The [`Regex`] requires to have at least one capture group.
+    /// # Panics
+    /// If the [`Regex`] cannot be used, see [`Template::try_with_regex`]
     /// # Example
     /// ```rust
     /// # use new_string_template::template::*;
@@ -94,14 +449,122 @@ impl Template {
     /// # let custom_regex = Regex::new(r"(.*)").unwrap();
     /// let templ = Template::new(template_string).with_regex(&custom_regex);
     /// ```
+    #[cfg(feature = "custom-regex")]
     #[must_use]
-    pub fn with_regex(mut self, regex: &Regex) -> Self {
-        self.matches = get_matches(regex, &self.src);
+    pub fn with_regex(self, regex: &Regex) -> Self {
+        return self.try_with_regex(regex).expect("Expected the Regex to be valid");
+    }
 
-        return self;
+    /// Change the [`Regex`] that is used to resolve the matches from the template string, like [`Template::with_regex`], but error on a invalid [`Regex`].  
+    /// Only available with the `custom-regex` feature.
+    ///
+    /// The value name is the capture group named `name` if there is one, otherwise the first capture group.  
+    /// The optional capture group named `default` is used as the default value (like `{data1:-default}`)
+    /// and the optional capture group named `filter` is used as the filter chain (like `upper | truncate(10)`, the leading `|` is optional).
+    /// # Errors
+    /// A [`TemplateErrorKind::InvalidRegex`] error is returned if the [`Regex`] has no capture group for the value name,
+    /// or if the capture group for the value name did not participate in a match (like a optional group `(\w+)?`).  
+    /// A [`TemplateErrorKind::InvalidSyntax`] error is returned if a `filter` capture group matched a invalid filter chain.
+    /// # Example
+    /// ```rust
+    /// # use new_string_template::template::*;
+    /// # use regex::Regex;
+    /// let templ = Template::new("Hello #name")
+    ///     .try_with_regex(&Regex::new(r"#(?P<name>\w+)").unwrap())
+    ///     .expect("Expected Result to be Ok");
+    ///
+    /// let rendered = templ.render_with(&vec![("name", "World")]).expect("Expected Result to be Ok");
+    /// assert_eq!("Hello World", rendered);
+    ///
+    /// assert!(Template::new("Hello #name").try_with_regex(&Regex::new(r"#\w+").unwrap()).is_err());
+    /// ```
+    #[cfg(feature = "custom-regex")]
+    pub fn try_with_regex(mut self, regex: &Regex) -> Result<Self, TemplateError> {
+        self.matches = get_matches(regex, &self.src)?;
+        self.nodes = build_tree(&self.src, &self.matches)?;
+        self.literal_len = literal_len(&self.nodes);
+
+        return Ok(self);
+    }
+
+    /// Change the [`Syntax`] that is used to resolve the matches from the template string
+    /// # Panics
+    /// If the matches of the [`Syntax`] cannot be used, see [`Template::try_with_syntax`]
+    /// # Example
+    /// ```rust
+    /// # use new_string_template::template::*;
+    /// # use new_string_template::syntax::Syntax;
+    /// let templ = Template::new("Hello {{ name }}").with_syntax(&Syntax::new("{{", "}}"));
+    /// ```
+    #[must_use]
+    pub fn with_syntax(self, syntax: &Syntax) -> Self {
+        return self.try_with_syntax(syntax).expect("Expected the Syntax to be valid");
+    }
+
+    /// Change the [`Syntax`] that is used to resolve the matches from the template string, like [`Template::with_syntax`], but error instead of panicking
+    /// # Errors
+    /// A [`TemplateErrorKind::InvalidSyntax`] error is returned if the matches of the [`Syntax`] cannot be used.  
+    /// A [`Syntax`] only finds placeholders without sections, so this currently does not error, use [`Syntax::try_new`] for invalid delimiters.
+    /// # Example
+    /// ```rust
+    /// # use new_string_template::template::*;
+    /// # use new_string_template::syntax::Syntax;
+    /// let syntax = Syntax::try_new("{{", "}}").expect("Expected Result to be Ok");
+    /// let templ = Template::new("Hello {{ name }}")
+    ///     .try_with_syntax(&syntax)
+    ///     .expect("Expected Result to be Ok");
+    /// ```
+    pub fn try_with_syntax(mut self, syntax: &Syntax) -> Result<Self, TemplateError> {
+        self.matches = syntax.scan(&self.src);
+        self.nodes = build_tree(&self.src, &self.matches)?;
+        self.literal_len = literal_len(&self.nodes);
+
+        return Ok(self);
+    }
+
+    /// Create a new Template Instance with a custom [`Syntax`]
+    /// # Panics
+    /// If the matches of the [`Syntax`] cannot be used, see [`Template::try_with_syntax`]
+    /// # Example
+    /// ```rust
+    /// # use new_string_template::template::*;
+    /// # use new_string_template::syntax::Syntax;
+    /// let templ = Template::new_syntax("Hello ${name}", &Syntax::new("${", "}"));
+    /// ```
+    pub fn new_syntax<T: Into<String>>(template: T, syntax: &Syntax) -> Self {
+        return Self::try_new_syntax(template, syntax).expect("Expected the Syntax to be valid");
+    }
+
+    /// Create a new Template Instance with a custom [`Syntax`], like [`Template::new_syntax`], but error instead of panicking
+    /// # Errors
+    /// See [`Template::try_with_syntax`]
+    /// # Example
+    /// ```rust
+    /// # use new_string_template::template::*;
+    /// # use new_string_template::syntax::Syntax;
+    /// let syntax = Syntax::try_new("${", "}").expect("Expected Result to be Ok");
+    /// let templ = Template::try_new_syntax("Hello ${name}", &syntax).expect("Expected Result to be Ok");
+    ///
+    /// let rendered = templ.render_with(&vec![("name", "World")]).expect("Expected Result to be Ok");
+    /// assert_eq!("Hello World", rendered);
+    /// ```
+    pub fn try_new_syntax<T: Into<String>>(template: T, syntax: &Syntax) -> Result<Self, TemplateError> {
+        let converted_string = template.into();
+        let matches = syntax.scan(&converted_string);
+        let nodes = build_tree(&converted_string, &matches)?;
+
+        return Ok(Self {
+            src: converted_string,
+            matches,
+            literal_len: literal_len(&nodes),
+            nodes,
+            filters: FilterRegistry::default(),
+        });
     }
 
-    /// Create a new Template Instance with a custom regex
+    /// Create a new Template Instance with a custom regex, only available with the `custom-regex` feature
+    /// # Panics
+    /// If the [`Regex`] cannot be used, see [`Template::try_new_regex`]
     /// # Example
     /// ```rust
     /// # use new_string_template::template::*;
@@ -110,137 +573,901 @@ impl Template {
     /// # let custom_regex = Regex::new(r"(.*)").unwrap();
     /// let templ = Template::new_regex(template_string, &custom_regex);
     /// ```
+    #[cfg(feature = "custom-regex")]
     pub fn new_regex<T: Into<String>>(template: T, regex: &Regex) -> Self {
+        return Self::try_new_regex(template, regex).expect("Expected the Regex to be valid");
+    }
+
+    /// Create a new Template Instance with a custom regex, like [`Template::new_regex`], but error on a invalid [`Regex`].  
+    /// Only available with the `custom-regex` feature.
+    /// # Errors
+    /// See [`Template::try_with_regex`]
+    /// # Example
+    /// ```rust
+    /// # use new_string_template::template::*;
+    /// # use regex::Regex;
+    /// let templ = Template::try_new_regex("Hello #name", &Regex::new(r"#(\w+)").unwrap()).expect("Expected Result to be Ok");
+    ///
+    /// let rendered = templ.render_with(&vec![("name", "World")]).expect("Expected Result to be Ok");
+    /// assert_eq!("Hello World", rendered);
+    ///
+    /// assert!(Template::try_new_regex("Hello #", &Regex::new(r"#(\w+)?").unwrap()).is_err());
+    /// ```
+    #[cfg(feature = "custom-regex")]
+    pub fn try_new_regex<T: Into<String>>(template: T, regex: &Regex) -> Result<Self, TemplateError> {
         let converted_string = template.into();
-        let matches = get_matches(regex, &converted_string);
-        return Self {
+        let matches = get_matches(regex, &converted_string)?;
+        let nodes = build_tree(&converted_string, &matches)?;
+
+        return Ok(Self {
             src: converted_string,
             matches,
-        };
+            literal_len: literal_len(&nodes),
+            nodes,
+            filters: FilterRegistry::default(),
+        });
     }
 
-    /// Render the template with the provided values.
+    /// Add a custom filter that can be used in placeholders as `{data1 | name}` or `{data1 | name(arg1, arg2)}`.
     ///
-    /// Internal Helper function for [`Template::render`], [`Template::render_string`] and [`Template::render_nofail`].
-    fn render_internal<T: AsRef<str>>(&self, values: &HashMap<&str, T>, fail: bool) -> Result<String, TemplateError> {
-        // Early return if there are no matches in the template string
-        if self.matches.is_empty() {
-            return Ok(self.src.clone());
-        }
+    /// A filter gets the current value and all arguments, and returns the new value.  
+    /// Custom filters take precedence over the built-in filters with the same name, the built-in filters are:
+    /// - `upper`, `lower`: convert the value to uppercase or lowercase
+    /// - `trim`, `trim_start`, `trim_end`: remove whitespace from the value
+    /// - `truncate(len)`: shorten the value to at most `len` characters
+    /// - `pad_left(len, fill)`, `pad_right(len, fill)`: pad the value to at least `len` characters with the characters of `fill` (default `" "`, can also be a single digit like `0`)
+    ///
+    /// Filters are only supported by the default syntax, not by custom regexes or [`Syntax`].
+    /// # Example
+    /// ```rust
+    /// # use new_string_template::template::*;
+    /// # use new_string_template::filter::FilterArg;
+    /// let mut templ = Template::new("Something {data1 | trim | repeat(2) | upper}");
+    /// templ.register_filter("repeat", |value: &str, args: &[FilterArg]| {
+    ///     let times = args.first().and_then(FilterArg::as_integer).ok_or("Expected a integer")?;
+    ///     return Ok(value.repeat(times as usize));
+    /// });
+    ///
+    /// let rendered = templ.render_with(&vec![("data1", " here ")]).expect("Expected Result to be Ok");
+    /// assert_eq!("Something HEREHERE", rendered);
+    /// ```
+    pub fn register_filter<N, F>(&mut self, name: N, filter: F)
+    where
+        N: Into<String>,
+        F: Fn(&str, &[FilterArg]) -> FilterResult + Send + Sync + 'static,
+    {
+        self.filters.insert(name.into(), Arc::new(filter));
+    }
 
-        // Start with an empty "Vec", but with at least the capacity of "self.matches"
-        let mut parts: Vec<&str> = Vec::with_capacity(self.matches.len());
-        // Save last index of an match, starting with "0"
-        let mut last_index: usize = 0;
+    /// Get all placeholders in the template, in the order they appear in the Template String.
+    ///
+    /// Placeholders that are used multiple times are returned for each time they are used, see [`Placeholder::count`].  
+    /// The conditions of `{#if}` and the lists of `{#each}` sections are also returned, see [`Placeholder::is_section`].
+    /// # Example
+    /// ```rust
+    /// # use new_string_template::template::*;
+    /// let templ = Template::new("Something {data1} be {data2}, and {data1}");
+    ///
+    /// let found: Vec<(&str, usize, usize)> = templ
+    ///     .placeholders()
+    ///     .map(|v| return (v.name(), v.span().start(), v.count()))
+    ///     .collect();
+    /// assert_eq!(vec![("data1", 10, 2), ("data2", 21, 1), ("data1", 34, 2)], found);
+    /// ```
+    pub fn placeholders(&self) -> impl Iterator<Item = Placeholder<'_>> {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
 
-        for entry in &self.matches {
-            parts.push(&self.src[last_index..entry.full_match_start]); // non-inclusive to only copy up-to just before the starting character of the beginning of the match
+        for entry in self.named_entries() {
+            *counts.entry(self.value_name(entry)).or_insert(0) += 1;
+        }
 
-            let arg_name = &self.src[entry.value_name_start..entry.value_name_end]; // non-inclusive because regex's "end" referes to the character after the match
+        // how many "{#each}" sections the current entry is in
+        let mut depth: usize = 0;
 
-            // not using "unwrap_or_else" because of the need to return "Err"
-            if let Some(v) = values.get(&arg_name) {
-                parts.push(v.as_ref());
-            } else {
-                if fail {
-                    return Err(TemplateError::new(
-                        TemplateErrorKind::MissingData,
-                        format!("Missing Data for Argument \"{}\"", &arg_name),
-                    ));
-                }
+        return self.matches.iter().filter_map(move |entry| {
+            let repeated = depth > 0;
 
-                // copy the full match in the template into the final string as a fallback if "fail" is "false"
-                parts.push(&self.src[entry.full_match_start..entry.full_match_end]);
-                // non-inclusive because regex's "end" referes to the character after the match
+            // the list of a "{#each}" is not repeated itself, only the entries after it are
+            match entry.kind {
+                MatchKind::Each => depth += 1,
+                MatchKind::EndEach => depth = depth.saturating_sub(1),
+                _ => (),
             }
 
-            last_index = entry.full_match_end;
-        }
+            let section = match entry.kind {
+                MatchKind::Placeholder => false,
+                MatchKind::If | MatchKind::Each => true,
+                _ => return None,
+            };
+            let name = self.value_name(entry);
 
-        // if string is not already fully copied, copy the rest of it
-        if last_index < self.src.len() {
-            parts.push(&self.src[last_index..self.src.len()]); // non-inclusive because "len" is last index + 1
-        }
+            return Some(Placeholder {
+                name,
+                span: Span::new(&self.src, entry.full_match_start, entry.full_match_end),
+                count: counts[name],
+                default_value: self.default_value(entry),
+                repeated,
+                section,
+            });
+        });
+    }
+
+    /// Get the names of all values required to render the template, deduplicated and sorted.
+    ///
+    /// Placeholders with a default value are not required, unless the same name is also used without a default value.  
+    /// The conditions of `{#if}` and the lists of `{#each}` sections are included,
+    /// but placeholders inside of `{#each}` sections are not, because they are provided by the items of the list.
+    /// # Example
+    /// ```rust
+    /// # use new_string_template::template::*;
+    /// let templ = Template::try_new("Something {data2} be {data1}, and {data2} {data3:-default}{#each jobs}{name}{/each}")
+    ///     .expect("Expected Result to be Ok");
+    ///
+    /// let keys: Vec<&str> = templ.required_keys().into_iter().collect();
+    /// assert_eq!(vec!["data1", "data2", "jobs"], keys);
+    /// ```
+    #[must_use]
+    pub fn required_keys(&self) -> BTreeSet<&str> {
+        return self
+            .placeholders()
+            .filter(|v| return !v.is_repeated() && v.default_value().is_none() && v.name() != ".")
+            .map(|v| return v.name())
+            .collect();
+    }
+
+    /// Get the default value of `entry` from the Template String, if any
+    fn default_value(&self, entry: &MatchEntry) -> Option<&str> {
+        return entry.default_value.map(|(start, end)| return &self.src[start..end]);
+    }
+
+    /// Get the separator of `entry` from the Template String, or a empty string if there is none
+    fn separator(&self, entry: &MatchEntry) -> &str {
+        return entry.separator.map_or("", |(start, end)| return &self.src[start..end]);
+    }
+
+    /// Get all the [`MatchEntry`]s that have a value name, which are placeholders and the `{#if}` and `{#each}` section tags
+    fn named_entries(&self) -> impl Iterator<Item = &MatchEntry> {
+        return self.matches.iter().filter(|entry| {
+            return match entry.kind {
+                MatchKind::Placeholder | MatchKind::If | MatchKind::Each => true,
+                _ => false,
+            };
+        });
+    }
 
-        return Ok(parts.join(""));
+    /// Get the value name of `entry` from the Template String
+    fn value_name(&self, entry: &MatchEntry) -> &str {
+        return &self.src[entry.value_name_start..entry.value_name_end]; // non-inclusive because regex's "end" referes to the character after the match
     }
 
     /// Render the template with the provided values.
-    fn render_internal_string<T: AsRef<str>>(
-        &self,
-        values: &HashMap<String, T>,
-        fail: bool,
-    ) -> Result<String, TemplateError> {
+    ///
+    /// Internal Helper function for all the render functions that return a [`String`].
+    fn render_internal<S: ValueSource + ?Sized>(&self, values: &S, fail: bool) -> Result<String, TemplateError> {
         // Early return if there are no matches in the template string
         if self.matches.is_empty() {
             return Ok(self.src.clone());
         }
 
-        // Start with an empty "Vec", but with at least the capacity of "self.matches"
-        let mut parts: Vec<&str> = Vec::with_capacity(self.matches.len());
-        // Save last index of an match, starting with "0"
-        let mut last_index: usize = 0;
+        let literal_len = match self.literal_len {
+            Some(v) => v,
+            None => {
+                // Start with at least the capacity of the template string, because most values are around the length of their placeholder
+                let mut out = String::with_capacity(self.src.len());
+                self.render_into(&mut out, values, fail)?;
 
-        for entry in &self.matches {
-            parts.push(&self.src[last_index..entry.full_match_start]); // non-inclusive to only copy up-to just before the starting character of the beginning of the match
+                return Ok(out);
+            },
+        };
 
-            let arg_name = &self.src[entry.value_name_start..entry.value_name_end]; // non-inclusive because regex's "end" referes to the character after the match
+        // Without sections every placeholder is rendered exactly once, so all values can be resolved first
+        // to allocate the output only once with the exact size
+        let mut resolved: Vec<Cow<'_, str>> = Vec::with_capacity(self.matches.len());
+        let mut len = literal_len;
 
-            // not using "unwrap_or_else" because of the need to return "Err"
-            if let Some(v) = values.get(&arg_name.to_string()) {
-                parts.push(v.as_ref());
-            } else {
-                if fail {
-                    return Err(TemplateError::new(
-                        TemplateErrorKind::MissingData,
-                        format!("Missing Data for Argument \"{}\"", &arg_name),
-                    ));
-                }
+        for entry in &self.matches {
+            let value = self.resolve_entry(entry, values, fail)?;
+            len += value.len();
+            resolved.push(value);
+        }
 
-                // copy the full match in the template into the final string as a fallback if "fail" is "false"
-                parts.push(&self.src[entry.full_match_start..entry.full_match_end]);
-                // non-inclusive because regex's "end" referes to the character after the match
-            }
+        let mut out = String::with_capacity(len);
+        let mut resolved = resolved.iter();
 
-            last_index = entry.full_match_end;
+        for node in &self.nodes {
+            match node {
+                Node::Text { start, end } => out.push_str(&self.src[*start..*end]),
+                Node::Match(_) => out.push_str(resolved.next().expect("Expected a value for every Match")),
+                Node::If { .. } | Node::Each { .. } => unreachable!("Expected no sections if \"literal_len\" is set"),
+            }
         }
 
-        // if string is not already fully copied, copy the rest of it
-        if last_index < self.src.len() {
-            parts.push(&self.src[last_index..self.src.len()]); // non-inclusive because "len" is last index + 1
+        return Ok(out);
+    }
+
+    /// Render the template with the provided values, borrowing the Template String as long as the output does not differ from it.
+    ///
+    /// Internal Helper function for all the render functions that return a [`Cow`].
+    fn render_cow_internal<S: ValueSource + ?Sized>(
+        &self,
+        values: &S,
+        fail: bool,
+    ) -> Result<Cow<'_, str>, TemplateError> {
+        // Early return if there are no matches in the template string
+        if self.matches.is_empty() {
+            return Ok(Cow::Borrowed(&self.src));
         }
 
-        return Ok(parts.join(""));
+        let mut out = CowWriter {
+            src: &self.src,
+            pos: 0,
+            out: None,
+        };
+        self.render_into(&mut out, values, fail)?;
+
+        return Ok(out.finish());
     }
 
-    /// Render the template with the provided values.
-    ///
-    /// This function takes a [`HashMap`] where the key is [`str`].
-    /// # Errors
-    /// This function Errors on the first problem encountered
-    /// # Example
-    /// ```rust
-    /// # use new_string_template::template::*;
-    /// # use std::collections::HashMap;
-    /// let templ_str = "Something {data1} be {data2}, and { not here }";
-    /// let templ = Template::new(templ_str);
-    /// let data = {
-    ///     let mut map = HashMap::new();
-    ///     map.insert("data1", "should");
-    ///     map.insert("data2", "here");
-    ///     map
-    /// };
+    /// Render the template with the provided values directly into `out`.
     ///
-    /// let rendered = templ.render(&data).expect("Expected Result to be Ok");
-    /// assert_eq!("Something should be here, and { not here }", rendered);
-    /// ```
-    pub fn render<T: AsRef<str>>(&self, values: &HashMap<&str, T>) -> Result<String, TemplateError> {
-        return self.render_internal(values, true);
+    /// Internal Helper function for all the render functions, segments are written as soon as they are resolved.
+    fn render_into<W: fmt::Write + ?Sized, S: ValueSource + ?Sized>(
+        &self,
+        out: &mut W,
+        values: &S,
+        fail: bool,
+    ) -> Result<(), TemplateError> {
+        return self.render_nodes(&self.nodes, out, values, fail);
     }
 
-    /// Render the template with the provided values.
+    /// Render all `nodes` with the provided values directly into `out`.
+    fn render_nodes<W: fmt::Write + ?Sized, S: ValueSource + ?Sized>(
+        &self,
+        nodes: &[Node],
+        out: &mut W,
+        values: &S,
+        fail: bool,
+    ) -> Result<(), TemplateError> {
+        for node in nodes {
+            match node {
+                Node::Text { start, end } => out.write_str(&self.src[*start..*end])?,
+                Node::Match(index) => self.render_entry(&self.matches[*index], out, values, fail)?,
+                Node::If {
+                    condition,
+                    then,
+                    otherwise,
+                } => {
+                    let branch = if self.is_truthy(&self.matches[*condition], values) {
+                        then
+                    } else {
+                        otherwise
+                    };
+
+                    self.render_nodes(branch, out, values, fail)?;
+                },
+                Node::Each { list, body, otherwise } => {
+                    let entry = &self.matches[*list];
+                    let items = match self.lookup_list(entry, values) {
+                        Ok(v) => v,
+                        Err(err) if fail => return Err(err),
+                        // render the else branch as a fallback if "fail" is "false"
+                        Err(_) => Vec::new(),
+                    };
+
+                    if items.is_empty() {
+                        self.render_nodes(otherwise, out, values, fail)?;
+                    }
+
+                    let outer = SourceRef(values);
+
+                    for (index, item) in items.into_iter().enumerate() {
+                        if index > 0 {
+                            out.write_str(self.separator(entry))?;
+                        }
+
+                        let scope = Scope {
+                            inner: item,
+                            outer: &outer,
+                        };
+                        self.render_nodes(body, out, &scope, fail)?;
+                    }
+                },
+            }
+        }
+
+        return Ok(());
+    }
+
+    /// Render a single placeholder or escape with the provided values directly into `out`.
+    fn render_entry<W: fmt::Write + ?Sized, S: ValueSource + ?Sized>(
+        &self,
+        entry: &MatchEntry,
+        out: &mut W,
+        values: &S,
+        fail: bool,
+    ) -> Result<(), TemplateError> {
+        out.write_str(&self.resolve_entry(entry, values, fail)?)?;
+
+        return Ok(());
+    }
+
+    /// Get the rendered text of a single placeholder or escape with the provided values.
+    fn resolve_entry<'a, S: ValueSource + ?Sized>(
+        &'a self,
+        entry: &MatchEntry,
+        values: &'a S,
+        fail: bool,
+    ) -> Result<Cow<'a, str>, TemplateError> {
+        match entry.kind {
+            // escapes are always rendered as their literal
+            MatchKind::Escape => return Ok(Cow::Borrowed(self.value_name(entry))),
+            // "{}" is rendered as-is if there are no positional values
+            MatchKind::Implicit(index) => {
+                return Ok(values
+                    .get_index(index)
+                    .unwrap_or_else(|| return Cow::Borrowed(&self.src[entry.full_match_start..entry.full_match_end])));
+            },
+            _ => (),
+        }
+
+        let value = match values.get_value(self.value_name(entry)) {
+            // the value is rendered as-is without filters and format spec, which is the most common case
+            Some(v) if entry.filters.is_empty() && entry.format.is_none() => return Ok(v),
+            Some(v) => Ok(v),
+            None => self.lookup_path(entry, values),
+        };
+
+        // typed numbers are only used without filters, because filters always return strings
+        let number = match entry.format {
+            Some(_) if entry.filters.is_empty() => get_number_path(values, self.value_name(entry)),
+            _ => None,
+        };
+
+        // not using "unwrap_or_else" because of the need to return "Err"
+        return match value
+            .and_then(|v| return self.apply_filters(entry, v))
+            .and_then(|v| return self.apply_format(entry, v, number))
+        {
+            Ok(v) => Ok(v),
+            Err(err) => {
+                if fail {
+                    return Err(err);
+                }
+
+                // copy the full match in the template into the final string as a fallback if "fail" is "false"
+                // non-inclusive because regex's "end" referes to the character after the match
+                Ok(Cow::Borrowed(&self.src[entry.full_match_start..entry.full_match_end]))
+            },
+        };
+    }
+
+    /// Get the value for the placeholder `entry` from `values`, falling back to its default value if the value is missing
+    fn lookup<'a, S: ValueSource + ?Sized>(
+        &'a self,
+        entry: &MatchEntry,
+        values: &'a S,
+    ) -> Result<Cow<'a, str>, TemplateError> {
+        if let Some(v) = values.get_value(self.value_name(entry)) {
+            return Ok(v);
+        }
+
+        return self.lookup_path(entry, values);
+    }
+
+    /// Get the value for the placeholder `entry` as a path, for when `values` has no value for the full name,
+    /// falling back to its default value if the value is missing
+    fn lookup_path<'a, S: ValueSource + ?Sized>(
+        &'a self,
+        entry: &MatchEntry,
+        values: &'a S,
+    ) -> Result<Cow<'a, str>, TemplateError> {
+        let path_err = match resolve_value_path(values, self.value_name(entry)) {
+            Ok(Some(v)) => return Ok(v),
+            Ok(None) => None,
+            Err(err) => Some(err),
+        };
+
+        if path_err.map_or(true, |err| return err.kind == PathErrorKind::Missing) {
+            if let Some(default) = self.default_value(entry) {
+                return Ok(Cow::Borrowed(default));
+            }
+        }
+
+        return Err(match path_err {
+            Some(err) => self.path_error(entry, err),
+            None => self.missing_data_error(entry),
+        });
+    }
+
+    /// Get the list for the repeated section `entry` from `values`
+    fn lookup_list<'a, S: ValueSource + ?Sized>(
+        &self,
+        entry: &MatchEntry,
+        values: &'a S,
+    ) -> Result<Vec<&'a dyn ValueSource>, TemplateError> {
+        let name = self.value_name(entry);
+
+        return match get_list_path(values, name) {
+            Ok(Some(v)) => Ok(v),
+            Ok(None) => match get_value_path(values, name) {
+                Ok(None) => Err(self.missing_data_error(entry)),
+                // a single value or a nested source (like a map)
+                _ => Err(self.entry_error(
+                    entry,
+                    TemplateErrorKind::NotAList,
+                    format!("Invalid Value for Section \"{}\": it is not a list", name),
+                )),
+            },
+            Err(err) => Err(self.path_error(entry, err)),
+        };
+    }
+
+    /// Get whether the value for the condition of a section (`entry`) is provided and not empty
+    fn is_truthy<S: ValueSource + ?Sized>(&self, entry: &MatchEntry, values: &S) -> bool {
+        let name = self.value_name(entry);
+
+        // typed booleans are used as-is, because "false" is not empty
+        if let Some(v) = get_bool_path(values, name) {
+            return v;
+        }
+
+        if let Ok(Some(list)) = get_list_path(values, name) {
+            return !list.is_empty();
+        }
+
+        return match get_value_path(values, name) {
+            Ok(v) => v.map_or(false, |v| return !v.is_empty()),
+            // nested sources (like maps) have no value, but are provided
+            Err(err) => err.kind == PathErrorKind::NotAScalar,
+        };
+    }
+
+    /// Check that `values` has a value for every placeholder in the template, without rendering it.
+    ///
+    /// Unlike the render functions, this function does not stop at the first problem.  
+    /// Placeholders in sections that would not be rendered with `values` are not checked.
+    /// # Errors
+    /// If any value is missing, a [`TemplateErrorKind::Multiple`] error is returned, which contains a
+    /// [`TemplateErrorKind::MissingData`] error (with [`Span`] and placeholder name) for every missing value, see [`TemplateError::errors`]  
+    /// Paths that are not valid for `values` are collected as [`TemplateErrorKind::NotAContainer`] errors,
+    /// values of repeated sections that are not lists as [`TemplateErrorKind::NotAList`] errors.
+    /// # Example
+    /// ```rust
+    /// # use new_string_template::template::*;
+    /// # use new_string_template::error::TemplateErrorKind;
+    /// let templ = Template::new("Something {data1} be {data2}, and {data3}");
+    /// let data = vec![("data1", "should")];
+    ///
+    /// let err = templ.check(&data).expect_err("Expected Result to be Err");
+    /// assert_eq!(TemplateErrorKind::Multiple, err.kind());
+    /// let missing: Vec<_> = err.errors().iter().filter_map(|v| return v.placeholder()).collect();
+    /// assert_eq!(vec!["data2", "data3"], missing);
+    /// ```
+    pub fn check<S: ValueSource + ?Sized>(&self, values: &S) -> Result<(), TemplateError> {
+        let mut errors: Vec<TemplateError> = Vec::new();
+        self.check_nodes(&self.nodes, values, &mut errors);
+
+        if errors.is_empty() {
+            return Ok(());
+        }
+
+        let names: Vec<String> = errors
+            .iter()
+            .map(|err| {
+                return format!(
+                    "\"{}\" at {}",
+                    err.placeholder().unwrap_or_default(),
+                    err.span().expect("Expected MissingData to have a Span")
+                );
+            })
+            .collect();
+
+        let problem = if errors
+            .iter()
+            .all(|err| return err.kind() == TemplateErrorKind::MissingData)
+        {
+            "Missing"
+        } else {
+            "Missing or Invalid"
+        };
+
+        return Err(TemplateError::new_multiple(
+            format!(
+                "{} Data for {} Argument(s): {}",
+                problem,
+                errors.len(),
+                names.join(", ")
+            ),
+            errors,
+        ));
+    }
+
+    /// Collect a error for every placeholder in `nodes` that would be rendered but is missing (or a invalid path) in `values`
+    fn check_nodes<S: ValueSource + ?Sized>(&self, nodes: &[Node], values: &S, errors: &mut Vec<TemplateError>) {
+        for node in nodes {
+            match node {
+                Node::Text { .. } => (),
+                Node::Match(index) => {
+                    let entry = &self.matches[*index];
+
+                    if entry.is_placeholder() {
+                        if let Err(err) = self.lookup(entry, values) {
+                            errors.push(err);
+                        }
+                    }
+                },
+                Node::If {
+                    condition,
+                    then,
+                    otherwise,
+                } => {
+                    let branch = if self.is_truthy(&self.matches[*condition], values) {
+                        then
+                    } else {
+                        otherwise
+                    };
+
+                    self.check_nodes(branch, values, errors);
+                },
+                Node::Each { list, body, otherwise } => {
+                    let items = match self.lookup_list(&self.matches[*list], values) {
+                        Ok(v) => v,
+                        Err(err) => {
+                            errors.push(err);
+                            continue;
+                        },
+                    };
+
+                    if items.is_empty() {
+                        self.check_nodes(otherwise, values, errors);
+                    }
+
+                    let outer = SourceRef(values);
+
+                    for item in items {
+                        let scope = Scope {
+                            inner: item,
+                            outer: &outer,
+                        };
+                        self.check_nodes(body, &scope, errors);
+                    }
+                },
+            }
+        }
+    }
+
+    /// Apply all filters of `entry` to `value`, in order
+    fn apply_filters<'a>(&self, entry: &MatchEntry, value: Cow<'a, str>) -> Result<Cow<'a, str>, TemplateError> {
+        let mut value = value;
+
+        for filter in &entry.filters {
+            let filter_name = &self.src[filter.name_start..filter.name_end];
+
+            value = match self.filters.apply(filter_name, &value, &filter.args) {
+                Some(Ok(v)) => Cow::Owned(v),
+                Some(Err(err)) => {
+                    return Err(self.entry_error(
+                        entry,
+                        TemplateErrorKind::Filter,
+                        format!("Filter \"{}\" failed: {}", filter_name, err),
+                    ));
+                },
+                None => {
+                    return Err(self.entry_error(
+                        entry,
+                        TemplateErrorKind::UnknownFilter,
+                        format!("Unknown Filter \"{}\"", filter_name),
+                    ));
+                },
+            };
+        }
+
+        return Ok(value);
+    }
+
+    /// Apply the format spec of `entry` (if any) to `value`, which is formatted as `number` instead if it is one
+    fn apply_format<'a>(
+        &self,
+        entry: &MatchEntry,
+        value: Cow<'a, str>,
+        number: Option<Number>,
+    ) -> Result<Cow<'a, str>, TemplateError> {
+        let spec = match entry.format {
+            Some(v) => v,
+            None => return Ok(value),
+        };
+
+        return spec
+            .format(&value, number)
+            .map(Cow::Owned)
+            .map_err(|err| return self.entry_error(entry, TemplateErrorKind::NotANumber, err));
+    }
+
+    /// Create a error of `kind` for `entry`, with the [`Span`] and placeholder name of `entry`
+    fn entry_error(&self, entry: &MatchEntry, kind: TemplateErrorKind, message: String) -> TemplateError {
+        return TemplateError::new(kind, message)
+            .with_span(Span::new(&self.src, entry.full_match_start, entry.full_match_end))
+            .with_placeholder(self.value_name(entry));
+    }
+
+    /// Create the [`TemplateErrorKind::MissingData`] error for `entry`
+    fn missing_data_error(&self, entry: &MatchEntry) -> TemplateError {
+        return self.entry_error(
+            entry,
+            TemplateErrorKind::MissingData,
+            format!("Missing Data for Argument \"{}\"", self.value_name(entry)),
+        );
+    }
+
+    /// Create the error for a path in `entry` that could not be resolved
+    fn path_error(&self, entry: &MatchEntry, err: PathError) -> TemplateError {
+        let name = self.value_name(entry);
+        let segment = &name[..err.end];
+
+        return match err.kind {
+            PathErrorKind::Missing => self.entry_error(
+                entry,
+                TemplateErrorKind::MissingData,
+                format!("Missing Data for Argument \"{}\": \"{}\" does not exist", name, segment),
+            ),
+            PathErrorKind::NotAContainer => self.entry_error(
+                entry,
+                TemplateErrorKind::NotAContainer,
+                format!("Invalid Path \"{}\": \"{}\" is not a map or list", name, segment),
+            ),
+            PathErrorKind::NotAScalar => self.entry_error(
+                entry,
+                TemplateErrorKind::NotAScalar,
+                format!("Invalid Value for Argument \"{}\": it is a map or list", name),
+            ),
+        };
+    }
+
+    /// Render the template with the provided values into a [`fmt::Write`] sink, without allocating a new [`String`].
+    ///
+    /// # Errors
+    /// This function Errors on the first problem encountered, or when writing into `out` fails.  
+    /// Everything before the problem will already have been written into `out`.
+    /// # Example
+    /// ```rust
+    /// # use new_string_template::template::*;
+    /// let templ = Template::new("Something {data1} be {data2}");
+    /// let data = vec![("data1", "should"), ("data2", "here")];
+    ///
+    /// let mut out = String::from("> ");
+    /// templ.render_to_fmt(&mut out, &data).expect("Expected Result to be Ok");
+    /// assert_eq!("> Something should be here", out);
+    /// ```
+    pub fn render_to_fmt<W: fmt::Write + ?Sized, S: ValueSource + ?Sized>(
+        &self,
+        out: &mut W,
+        values: &S,
+    ) -> Result<(), TemplateError> {
+        return self.render_into(out, values, true);
+    }
+
+    /// Render the template with the provided values into a [`io::Write`] sink, without allocating a new [`String`].
+    ///
+    /// # Errors
+    /// This function Errors on the first problem encountered, or with [`TemplateErrorKind::Io`] when writing into `out` fails.  
+    /// Everything before the problem will already have been written into `out`.
+    /// # Example
+    /// ```rust
+    /// # use new_string_template::template::*;
+    /// let templ = Template::new("Something {data1} be {data2}");
+    /// let data = vec![("data1", "should"), ("data2", "here")];
+    ///
+    /// let mut out: Vec<u8> = Vec::new();
+    /// templ.render_to_io(&mut out, &data).expect("Expected Result to be Ok");
+    /// assert_eq!(b"Something should be here", out.as_slice());
+    /// ```
+    pub fn render_to_io<W: io::Write + ?Sized, S: ValueSource + ?Sized>(
+        &self,
+        out: &mut W,
+        values: &S,
+    ) -> Result<(), TemplateError> {
+        let mut adapter = IoAdapter {
+            inner: out,
+            error: None,
+        };
+
+        return self.render_into(&mut adapter, values, true).map_err(|err| {
+            // prefer the actual io error over the opaque "fmt::Error" the adapter had to return
+            return match adapter.error.take() {
+                Some(io_err) => TemplateError::new(TemplateErrorKind::Io, io_err),
+                None => err,
+            };
+        });
+    }
+
+    /// Render the template with the values provided by any [`ValueSource`].
+    ///
+    /// This is the function all other render functions use, see [`ValueSource`] for what can be used as values.
+    /// # Errors
+    /// This function Errors on the first problem encountered
+    /// # Example
+    /// ```rust
+    /// # use new_string_template::template::*;
+    /// # use std::collections::BTreeMap;
+    /// let templ_str = "Something {data1} be {data2}, and { not here }";
+    /// let templ = Template::new(templ_str);
+    /// let data = {
+    ///     let mut map = BTreeMap::new();
+    ///     map.insert("data1", "should");
+    ///     map.insert("data2", "here");
+    ///     map
+    /// };
+    ///
+    /// let rendered = templ.render_with(&data).expect("Expected Result to be Ok");
+    /// assert_eq!("Something should be here, and { not here }", rendered);
+    ///
+    /// let rendered = templ
+    ///     .render_with(&|name: &str| return Some(name.len()))
+    ///     .expect("Expected Result to be Ok");
+    /// assert_eq!("Something 5 be 5, and { not here }", rendered);
+    /// ```
+    pub fn render_with<S: ValueSource + ?Sized>(&self, values: &S) -> Result<String, TemplateError> {
+        return self.render_internal(values, true);
+    }
+
+    /// Render the template with any [`Serialize`](serde::Serialize) value, only available with the `serde` feature
+    ///
+    /// The value is first converted into a [`Value`](crate::value::Value), see [`Value::from_serialize`](crate::value::Value::from_serialize),
+    /// so nested values can be accessed with paths like `{user.address.city}` and lists can be used with `{#each}`.
+    /// # Errors
+    /// This function Errors if `values` could not be serialized ([`TemplateErrorKind::Serialize`]),
+    /// if a placeholder is a map or list instead of a single value ([`TemplateErrorKind::NotAScalar`])
+    /// and on the first problem encountered, like [`Template::render_with`]
+    /// # Example
+    /// ```rust
+    /// # use new_string_template::template::*;
+    /// #[derive(serde::Serialize)]
+    /// struct Job {
+    ///     name:    &'static str,
+    ///     retries: u32,
+    ///     ok:      bool,
+    /// }
+    ///
+    /// let templ = Template::new("Job {name} (retries: {retries}, ok: {ok})");
+    /// let job = Job { name: "build", retries: 2, ok: false };
+    ///
+    /// let rendered = templ.render_serialize(&job).expect("Expected Result to be Ok");
+    /// assert_eq!("Job build (retries: 2, ok: false)", rendered);
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn render_serialize<T: serde::Serialize + ?Sized>(&self, values: &T) -> Result<String, TemplateError> {
+        let values = crate::value::Value::from_serialize(values)?;
+
+        return self.render_internal(&values, true);
+    }
+
+    /// Render the template with the values provided by any [`ValueSource`].
+    ///
+    /// This function always returns a [`String`], this function does not error or panic.  
+    /// If [`Template::render_with`] returned a [`Err`], this function will instead return the raw Template string.
+    /// # Example
+    /// ```rust
+    /// # use new_string_template::template::*;
+    /// let templ_str = "Something {data1} be {data2}, and { not here }";
+    /// let templ = Template::new(templ_str);
+    /// let data = vec![("data1", "should")];
+    ///
+    /// let rendered = templ.render_nofail_with(&data);
+    /// assert_eq!("Something should be {data2}, and { not here }", rendered);
+    /// ```
+    #[must_use]
+    pub fn render_nofail_with<S: ValueSource + ?Sized>(&self, values: &S) -> String {
+        return self
+            .render_internal(values, false)
+            .unwrap_or_else(|_| return self.src.clone());
+    }
+
+    /// Render the template with the values provided by any [`ValueSource`], like [`Template::render_with`],
+    /// but borrow the Template String instead of allocating a new [`String`] when rendering does not change it.
+    ///
+    /// The result is borrowed if there are no placeholders or escapes, or if every value is the same as its placeholder.
+    /// # Errors
+    /// This function Errors on the first problem encountered
+    /// # Example
+    /// ```rust
+    /// # use new_string_template::template::*;
+    /// # use std::borrow::Cow;
+    /// let data = vec![("data1", "should")];
+    ///
+    /// let templ = Template::new("Nothing to replace here");
+    /// let rendered = templ.render_cow(&data).expect("Expected Result to be Ok");
+    /// assert!(if let Cow::Borrowed(_) = rendered { true } else { false });
+    ///
+    /// let templ = Template::new("Something {data1} be here");
+    /// let rendered = templ.render_cow(&data).expect("Expected Result to be Ok");
+    /// assert_eq!("Something should be here", rendered);
+    /// ```
+    pub fn render_cow<S: ValueSource + ?Sized>(&self, values: &S) -> Result<Cow<'_, str>, TemplateError> {
+        return self.render_cow_internal(values, true);
+    }
+
+    /// Render the template with the values provided by any [`ValueSource`], like [`Template::render_nofail_with`],
+    /// but borrow the Template String instead of allocating a new [`String`] when rendering does not change it.
+    ///
+    /// The result is borrowed if there are no placeholders or escapes, or if every placeholder is left untouched because its value is missing.
+    /// # Example
+    /// ```rust
+    /// # use new_string_template::template::*;
+    /// # use std::borrow::Cow;
+    /// let templ = Template::new("Something {data1} be {data2}");
+    ///
+    /// let rendered = templ.render_nofail_cow(&vec![("data3", "unused")]);
+    /// assert!(if let Cow::Borrowed(_) = rendered { true } else { false });
+    ///
+    /// let rendered = templ.render_nofail_cow(&vec![("data1", "should")]);
+    /// assert_eq!("Something should be {data2}", rendered);
+    /// ```
+    #[must_use]
+    pub fn render_nofail_cow<S: ValueSource + ?Sized>(&self, values: &S) -> Cow<'_, str> {
+        return self
+            .render_cow_internal(values, false)
+            .unwrap_or_else(|_| return Cow::Borrowed(&self.src));
+    }
+
+    /// Render the template with the provided values.
+    ///
+    /// This function takes a [`HashMap`] where the key is [`str`] and the values are strings,
+    /// see [`Template::render_values`] for typed values and [`Template::render_with`] for other value sources.
+    /// # Errors
+    /// This function Errors on the first problem encountered
+    /// # Example
+    /// ```rust
+    /// # use new_string_template::template::*;
+    /// # use std::collections::HashMap;
+    /// let templ_str = "Something {data1} be {data2}, and { not here }";
+    /// let templ = Template::new(templ_str);
+    /// let data = {
+    ///     let mut map = HashMap::new();
+    ///     map.insert("data1", "should");
+    ///     map.insert("data2", "here");
+    ///     map
+    /// };
+    ///
+    /// let rendered = templ.render(&data).expect("Expected Result to be Ok");
+    /// assert_eq!("Something should be here, and { not here }", rendered);
+    /// ```
+    pub fn render<T: AsRef<str>>(&self, values: &HashMap<&str, T>) -> Result<String, TemplateError> {
+        return self.render_internal(values, true);
+    }
+
+    /// Render the template with the provided typed values.
+    ///
+    /// This function takes a [`HashMap`] of [`Value`]s (which can be created with [`Value::from`] for common types),
+    /// so numbers can be used with numeric format specs like `{price:.2}` and booleans are used as-is for `{#if}` sections.  
+    /// Lists and maps can be used with `{#each}` sections and paths like `{user.name}`, see [`Value`].
+    /// # Errors
+    /// This function Errors on the first problem encountered
+    /// # Example
+    /// ```rust
+    /// # use new_string_template::template::*;
+    /// # use new_string_template::value::Value;
+    /// # use std::collections::HashMap;
+    /// let templ = Template::try_new("{item}: {price:.2}{#if sold_out} (sold out){/if}")
+    ///     .expect("Expected Result to be Ok")
+    ///     .with_format_specs();
+    /// let mut data = HashMap::new();
+    /// data.insert("item", Value::from("Coffee"));
+    /// data.insert("price", Value::from(2.5));
+    /// data.insert("sold_out", Value::from(false));
+    ///
+    /// let rendered = templ.render_values(&data).expect("Expected Result to be Ok");
+    /// assert_eq!("Coffee: 2.50", rendered);
+    /// ```
+    pub fn render_values<K: Borrow<str> + Eq + Hash>(
+        &self,
+        values: &HashMap<K, Value>,
+    ) -> Result<String, TemplateError> {
+        return self.render_internal(values, true);
+    }
+
+    /// Render the template with the provided values.
     ///
-    /// This function takes a [`HashMap`] where the key is [`String`].
+    /// This function takes a [`HashMap`] where the key is [`String`], see [`Template::render_with`] for other value sources.
     /// # Errors
     /// This function Errors on the first problem encountered
     /// # Example
@@ -260,12 +1487,12 @@ impl Template {
     /// assert_eq!("Something should be here, and { not here }", rendered);
     /// ```
     pub fn render_string<T: AsRef<str>>(&self, values: &HashMap<String, T>) -> Result<String, TemplateError> {
-        return self.render_internal_string(values, true);
+        return self.render_internal(values, true);
     }
 
     /// Render the template with the provided values.
     ///
-    /// This function takes a [`HashMap`] where the key is [`str`].  
+    /// This function takes a [`HashMap`] where the key is [`str`], see [`Template::render_nofail_with`] for other value sources.  
     /// This function always returns a [`String`], this function does not error or panic.  
     /// If [`Template::render`] returned a [`Err`], this function will instead return the raw Template string.
     /// # Example
@@ -293,7 +1520,7 @@ impl Template {
 
     /// Render the template with the provided values.
     ///
-    /// This function takes a [`HashMap`] where the key is [`String`].  
+    /// This function takes a [`HashMap`] where the key is [`String`], see [`Template::render_nofail_with`] for other value sources.  
     /// This function always returns a [`String`], this function does not error or panic.  
     /// If [`Template::render_string`] returned a [`Err`], this function will instead return the raw Template string.
     /// # Example
@@ -315,33 +1542,563 @@ impl Template {
     #[must_use]
     pub fn render_nofail_string<T: AsRef<str>>(&self, values: &HashMap<String, T>) -> String {
         return self
-            .render_internal_string(values, false)
+            .render_internal(values, false)
             .unwrap_or_else(|_| return self.src.clone());
     }
-}
 
-/// Helper function to execute a [`Regex`] and get all the matches as [`MatchEntry`]
-fn get_matches(regex: &Regex, template: &str) -> Vec<MatchEntry> {
-    return regex
-        .captures_iter(template)
-        .map(|found| {
-            let full_match = found.get(0).expect("Match Index 0 was None (Full Match)");
-            let value_match = found.get(1).expect("Match Index 1 was None (Inner Match)");
+    /// Render the template with positional values, like [`format!`].
+    ///
+    /// Placeholders with index (`{0}`, `{1}`) use the value at that index, placeholders without index (`{}`) use the next value.
+    /// Both kinds cannot be used in the same template, but placeholders with index can be used multiple times and in any order.  
+    /// Placeholders with index can use filters and default values like named placeholders (`{0 | upper}`),
+    /// named placeholders have no value, so they are only rendered if they have a default value.
+    ///
+    /// With the other render functions, `{}` is rendered as-is and `{0}` is a named placeholder with the name `0`.
+    /// # Errors
+    /// A [`TemplateErrorKind::IndexOutOfRange`] error is returned if there are not enough values for all positional placeholders.  
+    /// A [`TemplateErrorKind::MixedPositional`] error is returned if placeholders with and without index are used in the same template.  
+    /// Otherwise this function Errors on the first problem encountered, like [`Template::render_with`]
+    /// # Example
+    /// ```rust
+    /// # use new_string_template::template::*;
+    /// # use new_string_template::error::TemplateErrorKind;
+    /// let templ = Template::new("{} + {} = {}");
+    /// let rendered = templ.render_positional(&[1, 2, 3]).expect("Expected Result to be Ok");
+    /// assert_eq!("1 + 2 = 3", rendered);
+    ///
+    /// let templ = Template::new("{1}, {0 | upper} and {1} again");
+    /// let rendered = templ.render_positional(&["first", "second"]).expect("Expected Result to be Ok");
+    /// assert_eq!("second, FIRST and second again", rendered);
+    ///
+    /// let err = templ.render_positional(&["first"]).expect_err("Expected Result to be Err");
+    /// assert_eq!(TemplateErrorKind::IndexOutOfRange, err.kind());
+    ///
+    /// let err = Template::new("{} {0}").render_positional(&["first"]).expect_err("Expected Result to be Err");
+    /// assert_eq!(TemplateErrorKind::MixedPositional, err.kind());
+    /// ```
+    pub fn render_positional<T: fmt::Display>(&self, values: &[T]) -> Result<String, TemplateError> {
+        self.check_positional(values.len())?;
 
-            return MatchEntry::new(
-                (full_match.start(), full_match.end()),
-                (value_match.start(), value_match.end()),
-            );
-        })
-        .collect();
-}
+        return self.render_internal(&Positional(values), true);
+    }
 
-#[cfg(test)]
-mod test {
-    use crate::error::TemplateErrorKind;
+    /// Render the template with typed positional values, like [`Template::render_positional`]
+    ///
+    /// The values of [`Template::render_positional`] are never numbers, so numeric format specs like `{0:.2}` require typed values.
+    /// # Errors
+    /// This function Errors like [`Template::render_positional`]
+    /// # Example
+    /// ```rust
+    /// # use new_string_template::template::*;
+    /// # use new_string_template::value::Value;
+    /// let templ = Template::new("{0:>5}: {1:.2} ({2:05})").with_format_specs();
+    /// let rendered = templ
+    ///     .render_positional_values(&[Value::from("price"), Value::from(4.5), Value::from(42)])
+    ///     .expect("Expected Result to be Ok");
+    /// assert_eq!("price: 4.50 (00042)", rendered);
+    /// ```
+    pub fn render_positional_values(&self, values: &[Value]) -> Result<String, TemplateError> {
+        self.check_positional(values.len())?;
+
+        return self.render_internal(&Positional(values), true);
+    }
+
+    /// Check that all positional placeholders have a value in a slice of `len` values, and that they are not mixed
+    fn check_positional(&self, len: usize) -> Result<(), TemplateError> {
+        let mut implicit = false;
+        let mut explicit = false;
+
+        for entry in &self.matches {
+            let index = match entry.kind {
+                MatchKind::Implicit(index) => {
+                    implicit = true;
+                    index
+                },
+                MatchKind::Placeholder => match explicit_index(self.value_name(entry)) {
+                    Some(index) => {
+                        explicit = true;
+                        index
+                    },
+                    None => continue,
+                },
+                _ => continue,
+            };
+
+            if implicit && explicit {
+                return Err(TemplateError::new(
+                    TemplateErrorKind::MixedPositional,
+                    "Positional placeholders with index (\"{0}\") and without index (\"{}\") cannot be mixed"
+                        .to_string(),
+                )
+                .with_span(Span::new(&self.src, entry.full_match_start, entry.full_match_end)));
+            }
+
+            if index >= len {
+                return Err(TemplateError::new(
+                    TemplateErrorKind::IndexOutOfRange,
+                    format!("Missing Data for Index {}, there are only {} values", index, len),
+                )
+                .with_span(Span::new(&self.src, entry.full_match_start, entry.full_match_end)));
+            }
+        }
+
+        return Ok(());
+    }
+
+    /// Resolve every placeholder to a position in `keys` once, for rendering the template many times with the same keys.
+    ///
+    /// The returned [`BoundTemplate`] renders from a slice of values in the same order as `keys`, without looking up any names.  
+    /// Placeholders whose name is not in `keys` are always rendered as their default value.
+    /// # Errors
+    /// A [`TemplateErrorKind::MissingData`] error is returned for the first placeholder whose name is not in `keys` and that has no default value.  
+    /// A [`TemplateErrorKind::InvalidSyntax`] error is returned if the template has sections or numeric format specs (like `{id:08x}`),
+    /// which cannot be rendered from a slice of strings.
+    /// # Example
+    /// ```rust
+    /// # use new_string_template::template::*;
+    /// let templ = Template::new("{level}: {message | upper} ({code:-none})");
+    /// let bound = templ.bind(&["message", "level"]).expect("Expected Result to be Ok");
+    ///
+    /// let rendered = bound.render(&["disk full", "error"]).expect("Expected Result to be Ok");
+    /// assert_eq!("error: DISK FULL (none)", rendered);
+    ///
+    /// assert!(templ.bind(&["message"]).is_err());
+    /// ```
+    pub fn bind<T: AsRef<str>>(&self, keys: &[T]) -> Result<BoundTemplate<'_>, TemplateError> {
+        let mut nodes = Vec::with_capacity(self.nodes.len());
+
+        for node in &self.nodes {
+            nodes.push(match node {
+                Node::Text { start, end } => BoundNode::Literal(*start, *end),
+                Node::Match(index) => {
+                    let entry = &self.matches[*index];
+                    let name = self.value_name(entry);
+
+                    if entry.is_implicit() {
+                        // "{}" is rendered as-is, because there are no positional values
+                        BoundNode::Literal(entry.full_match_start, entry.full_match_end)
+                    } else if !entry.is_placeholder() {
+                        // escapes are always rendered as their literal
+                        BoundNode::Literal(entry.value_name_start, entry.value_name_end)
+                    } else if entry.format.map_or(false, |v| return v.is_numeric()) {
+                        return Err(self.entry_error(
+                            entry,
+                            TemplateErrorKind::InvalidSyntax,
+                            "Numeric format specs cannot be used in a bound Template, because the values are not typed numbers"
+                                .to_string(),
+                        ));
+                    } else if let Some(position) = keys.iter().position(|key| return key.as_ref() == name) {
+                        BoundNode::Value(position, *index)
+                    } else if entry.default_value.is_some() {
+                        BoundNode::Default(*index)
+                    } else {
+                        return Err(self.missing_data_error(entry));
+                    }
+                },
+                Node::If { condition: index, .. } | Node::Each { list: index, .. } => {
+                    return Err(self.entry_error(
+                        &self.matches[*index],
+                        TemplateErrorKind::InvalidSyntax,
+                        "Sections cannot be used in a bound Template".to_string(),
+                    ));
+                },
+            });
+        }
+
+        return Ok(BoundTemplate {
+            template: self,
+            nodes,
+            keys: keys.len(),
+        });
+    }
+}
+
+/// A part of a [`BoundTemplate`]
+#[derive(Debug, Clone, PartialEq)]
+enum BoundNode {
+    /// Text from the Template String (literal text or an escape), from start to end
+    Literal(usize, usize),
+    /// The value at the position in the values, for the [`MatchEntry`] at the index
+    Value(usize, usize),
+    /// The default value of the [`MatchEntry`] at the index
+    Default(usize),
+}
+
+/// A [`Template`] with every placeholder resolved to a position in a fixed list of keys, see [`Template::bind`]
+#[derive(Debug, Clone)]
+pub struct BoundTemplate<'a> {
+    /// The bound Template
+    template: &'a Template,
+    /// The parts of the Template, in order
+    nodes:    Vec<BoundNode>,
+    /// The number of keys the Template was bound to
+    keys:     usize,
+}
+
+impl BoundTemplate<'_> {
+    /// Render the template with `values` in the same order as the keys given to [`Template::bind`].
+    ///
+    /// The output is allocated once with the exact size, unless filters change the length of values.
+    /// # Errors
+    /// A [`TemplateErrorKind::MissingData`] error is returned if the number of `values` is not the same as the number of keys.  
+    /// A [`TemplateErrorKind::Filter`] error is returned if a filter fails.
+    /// # Example
+    /// ```rust
+    /// # use new_string_template::template::*;
+    /// let templ = Template::new("Hello {name}, you are {age} years old");
+    /// let bound = templ.bind(&["name", "age"]).expect("Expected Result to be Ok");
+    ///
+    /// for (name, age) in &[("Alice", "30"), ("Bob", "25")] {
+    ///     let rendered = bound.render(&[name, age]).expect("Expected Result to be Ok");
+    ///     assert_eq!(format!("Hello {}, you are {} years old", name, age), rendered);
+    /// }
+    /// ```
+    pub fn render<T: AsRef<str>>(&self, values: &[T]) -> Result<String, TemplateError> {
+        if values.len() != self.keys {
+            return Err(TemplateError::new(
+                TemplateErrorKind::MissingData,
+                format!(
+                    "Expected {} values (one for every bound key), got {}",
+                    self.keys,
+                    values.len()
+                ),
+            ));
+        }
+
+        let templ = self.template;
+        let capacity = self
+            .nodes
+            .iter()
+            .map(|node| {
+                return match node {
+                    BoundNode::Literal(start, end) => end - start,
+                    BoundNode::Value(position, _) => values[*position].as_ref().len(),
+                    BoundNode::Default(index) => templ.default_value(&templ.matches[*index]).map_or(0, str::len),
+                };
+            })
+            .sum();
+        let mut out = String::with_capacity(capacity);
+
+        for node in &self.nodes {
+            match node {
+                BoundNode::Literal(start, end) => out.push_str(&templ.src[*start..*end]),
+                BoundNode::Value(position, index) => {
+                    let entry = &templ.matches[*index];
+                    let value = templ.apply_filters(entry, Cow::Borrowed(values[*position].as_ref()))?;
+
+                    // the values are strings, so there are no typed numbers for format specs
+                    out.push_str(&templ.apply_format(entry, value, None)?);
+                },
+                BoundNode::Default(index) => {
+                    let entry = &templ.matches[*index];
+                    let default = templ.default_value(entry).unwrap_or_default();
+                    let value = templ.apply_filters(entry, Cow::Borrowed(default))?;
+
+                    out.push_str(&templ.apply_format(entry, value, None)?);
+                },
+            }
+        }
+
+        return Ok(out);
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Template {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        return serializer.serialize_str(&self.src);
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Template {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        return deserializer.deserialize_string(TemplateVisitor);
+    }
+}
+
+/// Visitor to deserialize a [`Template`] from a string
+#[cfg(feature = "serde")]
+struct TemplateVisitor;
+
+#[cfg(feature = "serde")]
+impl serde::de::Visitor<'_> for TemplateVisitor {
+    type Value = Template;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return f.write_str("a template string");
+    }
+
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Template, E> {
+        return self.visit_string(v.to_string());
+    }
+
+    fn visit_string<E: serde::de::Error>(self, v: String) -> Result<Template, E> {
+        return Template::try_new(v).map_err(E::custom);
+    }
+}
+
+/// Adapter to write into a [`io::Write`] through [`fmt::Write`], keeping the original [`io::Error`]
+struct IoAdapter<'a, W: io::Write + ?Sized> {
+    inner: &'a mut W,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write + ?Sized> fmt::Write for IoAdapter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        return self.inner.write_all(s.as_bytes()).map_err(|err| {
+            self.error = Some(err);
+
+            return fmt::Error;
+        });
+    }
+}
+
+/// Helper function to execute a custom [`Regex`] and get all the matches as [`MatchEntry`],
+/// the capture group named `name` (or capture group 1 if there is none) is the value name,
+/// the optional capture groups named `default` and `filter` are the default value and the filter chain
+///
+/// Errors with [`TemplateErrorKind::InvalidRegex`] if there is no capture group for the value name or it did not participate in a match
+#[cfg(feature = "custom-regex")]
+fn get_matches(regex: &Regex, template: &str) -> Result<Vec<MatchEntry>, TemplateError> {
+    let named = regex.capture_names().any(|name| return name == Some("name"));
+
+    if !named && regex.captures_len() < 2 {
+        return Err(TemplateError::new(
+            TemplateErrorKind::InvalidRegex,
+            format!("Regex \"{}\" has no capture group for the value name", regex.as_str()),
+        ));
+    }
+
+    return regex
+        .captures_iter(template)
+        .map(|found| {
+            let full_match = found.get(0).expect("Match Index 0 was None (Full Match)");
+            let span = Span::new(template, full_match.start(), full_match.end());
+            let value_match = if named { found.name("name") } else { found.get(1) };
+            let value_match = match value_match {
+                Some(v) => v,
+                None => {
+                    return Err(TemplateError::new(
+                        TemplateErrorKind::InvalidRegex,
+                        format!(
+                            "Regex \"{}\" matched without the capture group for the value name",
+                            regex.as_str()
+                        ),
+                    )
+                    .with_span(span))
+                },
+            };
+
+            let filters = match found.name("filter") {
+                Some(chain) if !is_filter_chain(chain.as_str()) => {
+                    return Err(TemplateError::new(
+                        TemplateErrorKind::InvalidSyntax,
+                        format!("Invalid filter chain \"{}\"", chain.as_str()),
+                    )
+                    .with_span(span))
+                },
+                Some(chain) => parse_filters(chain.as_str(), chain.start()),
+                None => Vec::new(),
+            };
+
+            return Ok(MatchEntry::new(
+                (full_match.start(), full_match.end()),
+                (value_match.start(), value_match.end()),
+            )
+            .with_default(found.name("default").map(|v| return (v.start(), v.end())))
+            .with_filters(filters));
+        })
+        .collect();
+}
+
+/// Helper function to get the total length of all literal text in `nodes`, or [`None`] if there are sections
+fn literal_len(nodes: &[Node]) -> Option<usize> {
+    let mut len = 0;
+
+    for node in nodes {
+        match node {
+            Node::Text { start, end } => len += end - start,
+            Node::Match(_) => (),
+            Node::If { .. } | Node::Each { .. } => return None,
+        }
+    }
+
+    return Some(len);
+}
+
+/// A [`fmt::Write`] that only allocates once the output differs from the Template String
+///
+/// As long as everything written is the next part of `src`, only the position in `src` is tracked.
+struct CowWriter<'a> {
+    /// The Template String
+    src: &'a str,
+    /// The length of the output, while it is still the same as the beginning of `src`
+    pos: usize,
+    /// The output, once it differs from `src`
+    out: Option<String>,
+}
+
+impl<'a> CowWriter<'a> {
+    /// Get the output, borrowing `src` if it never differed
+    fn finish(self) -> Cow<'a, str> {
+        return match self.out {
+            Some(out) => Cow::Owned(out),
+            None => Cow::Borrowed(&self.src[..self.pos]),
+        };
+    }
+}
+
+impl fmt::Write for CowWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if let Some(out) = &mut self.out {
+            out.push_str(s);
+        } else if self.src[self.pos..].starts_with(s) {
+            self.pos += s.len();
+        } else {
+            // Start with at least the capacity of the template string, because most values are around the length of their placeholder
+            let mut out = String::with_capacity(self.src.len().max(self.pos + s.len()));
+            out.push_str(&self.src[..self.pos]);
+            out.push_str(s);
+            self.out = Some(out);
+        }
+
+        return Ok(());
+    }
+}
+
+/// Helper function to scan `src` with the default syntax and build the [`Node`] tree for [`Template::new`]
+///
+/// If the sections are invalid, all section tags are treated as normal text.
+fn scan_lenient(src: &str, format_specs: bool) -> (Vec<MatchEntry>, Vec<Node>) {
+    let mut matches = scan(src, format_specs);
+
+    let nodes = build_tree(src, &matches).unwrap_or_else(|_| {
+        // fallback to not have any sections, which cannot fail
+        matches.retain(|entry| return !entry.is_section());
+
+        return build_tree(src, &matches).expect("Expected a Template without sections to be valid");
+    });
+
+    return (matches, nodes);
+}
+
+/// Helper function to build the [`Node`] tree from all `matches` in the Template String `src`
+///
+/// Errors with [`TemplateErrorKind::InvalidSyntax`] if the sections are not balanced
+fn build_tree(src: &str, matches: &[MatchEntry]) -> Result<Vec<Node>, TemplateError> {
+    /// Get the list new nodes are currently added to
+    fn current<'a>(root: &'a mut Vec<Node>, open: &'a mut [OpenSection]) -> &'a mut Vec<Node> {
+        return match open.last_mut() {
+            Some(section) => section.current(),
+            None => root,
+        };
+    }
+
+    let syntax_error = |entry: &MatchEntry, message: &str| {
+        return TemplateError::new(TemplateErrorKind::InvalidSyntax, message.to_string()).with_span(Span::new(
+            src,
+            entry.full_match_start,
+            entry.full_match_end,
+        ));
+    };
+
+    let mut root: Vec<Node> = Vec::new();
+    let mut open: Vec<OpenSection> = Vec::new();
+    // Save last index of an match, starting with "0"
+    let mut last_index: usize = 0;
+
+    for (index, entry) in matches.iter().enumerate() {
+        if last_index < entry.full_match_start {
+            current(&mut root, &mut open).push(Node::Text {
+                start: last_index,
+                end:   entry.full_match_start,
+            });
+        }
+
+        match entry.kind {
+            MatchKind::Placeholder | MatchKind::Escape | MatchKind::Implicit(_) => {
+                current(&mut root, &mut open).push(Node::Match(index));
+            },
+            MatchKind::If | MatchKind::Each => open.push(OpenSection {
+                start:     index,
+                then:      Vec::new(),
+                otherwise: None,
+            }),
+            MatchKind::Else => match open.last_mut() {
+                Some(section) if section.otherwise.is_none() => section.otherwise = Some(Vec::new()),
+                Some(_) => return Err(syntax_error(entry, "Duplicate \"{#else}\" in section")),
+                None => return Err(syntax_error(entry, "Found \"{#else}\" outside of a section")),
+            },
+            MatchKind::EndIf | MatchKind::EndEach => {
+                let name = entry.kind.section_name();
+                let section = open.pop().ok_or_else(|| {
+                    return syntax_error(entry, &format!("Found \"{{/{0}}}\" without \"{{#{0}}}\"", name));
+                })?;
+                let start_kind = matches[section.start].kind;
+
+                if start_kind.section_name() != name {
+                    return Err(syntax_error(
+                        entry,
+                        &format!(
+                            "Found \"{{/{}}}\" but expected \"{{/{}}}\"",
+                            name,
+                            start_kind.section_name()
+                        ),
+                    ));
+                }
+
+                let otherwise = section.otherwise.unwrap_or_default();
+
+                current(&mut root, &mut open).push(if start_kind == MatchKind::Each {
+                    Node::Each {
+                        list: section.start,
+                        body: section.then,
+                        otherwise,
+                    }
+                } else {
+                    Node::If {
+                        condition: section.start,
+                        then: section.then,
+                        otherwise,
+                    }
+                });
+            },
+        }
+
+        last_index = entry.full_match_end;
+    }
+
+    if let Some(section) = open.last() {
+        let entry = &matches[section.start];
+
+        return Err(syntax_error(
+            entry,
+            &format!("Found \"{{#{0}}}\" without \"{{/{0}}}\"", entry.kind.section_name()),
+        ));
+    }
+
+    // if string is not already fully covered, add the rest of it
+    if last_index < src.len() {
+        root.push(Node::Text {
+            start: last_index,
+            end:   src.len(),
+        });
+    }
+
+    return Ok(root);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        error::TemplateErrorKind,
+        value::Value,
+    };
 
     use super::*;
 
+    #[cfg(feature = "custom-regex")]
     #[test]
     fn test_regex_end_plus1() {
         // this test tests that "regex::Match::end" returns the index of the next character than the match length
@@ -447,6 +2204,7 @@ mod test {
         assert_eq!("Something should be {data2}, and { not here }", rendered);
     }
 
+    #[cfg(feature = "custom-regex")]
     #[test]
     fn test_render_custom_regex_double_brackets() {
         let custom_regex = Regex::new(r"(?mi)\{\{\s+([^\}]+)\s+\}\}").unwrap();
@@ -464,6 +2222,7 @@ mod test {
         assert_eq!("Something should be here, and here too", rendered);
     }
 
+    #[cfg(feature = "custom-regex")]
     #[test]
     fn test_render_custom_regex_single() {
         let custom_regex = Regex::new(r"(?mi)#(\S+)").unwrap();
@@ -479,6 +2238,7 @@ mod test {
         assert_eq!("Signle character can be seen here", rendered);
     }
 
+    #[cfg(feature = "custom-regex")]
     #[test]
     fn test_new_regex() {
         let custom_regex = Regex::new(r"(?mi)#(\S+)").unwrap();
@@ -494,6 +2254,64 @@ mod test {
         assert_eq!("Signle character can be seen here", rendered);
     }
 
+    #[cfg(feature = "custom-regex")]
+    #[test]
+    fn test_try_new_regex() {
+        let data = vec![("data1", "one"), ("data2", "two")];
+
+        let templ = Template::try_new_regex("#data1 <data2>", &Regex::new(r"(#)?<(?P<name>\w+)>").unwrap())
+            .expect("Expected Result to be Ok");
+        assert_eq!(
+            "#data1 two",
+            templ.render_with(&data).expect("Expected Result to be Ok")
+        );
+
+        // the named group is used even if there are other groups
+        let err = Template::try_new_regex("#data1 <data2>", &Regex::new(r"<(?P<name>\w+)>|#(\w+)").unwrap())
+            .expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::InvalidRegex, err.kind());
+
+        let err =
+            Template::try_new_regex("#data1", &Regex::new(r"#\w+").unwrap()).expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::InvalidRegex, err.kind());
+        assert!(err.span().is_none());
+
+        let err = Template::new("#data1 # #data2")
+            .try_with_regex(&Regex::new(r"#(\w+)?").unwrap())
+            .expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::InvalidRegex, err.kind());
+        assert_eq!(Some((1, 8)), err.span().map(|v| return (v.line(), v.column())));
+    }
+
+    #[cfg(feature = "custom-regex")]
+    #[test]
+    fn test_custom_regex_named_groups() {
+        let custom_regex =
+            Regex::new(r"\$\{(?P<name>\w+)(?:\s*\|(?P<filter>[^}:]*))?(?::(?P<default>[^}]*))?\}|\$(\w+)").unwrap();
+        let templ = Template::try_new_regex("${data1|upper} ${data2:two} ${data3 | upper:three}", &custom_regex)
+            .expect("Expected Result to be Ok");
+        let data = vec![("data1", "one")];
+
+        let rendered = templ.render_with(&data).expect("Expected Result to be Ok");
+        assert_eq!("ONE two THREE", rendered);
+
+        let templ =
+            Template::try_new_regex("${data1 | upper | truncate(2)}", &custom_regex).expect("Expected Result to be Ok");
+        let rendered = templ.render_with(&data).expect("Expected Result to be Ok");
+        assert_eq!("ON", rendered);
+
+        let err = Template::try_new_regex("${data1 | upper(}", &custom_regex).expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::InvalidSyntax, err.kind());
+        assert_eq!(Some((1, 1)), err.span().map(|v| return (v.line(), v.column())));
+    }
+
+    #[cfg(feature = "custom-regex")]
+    #[test]
+    #[should_panic(expected = "Expected the Regex to be valid")]
+    fn test_new_regex_invalid() {
+        let _ = Template::new_regex("#data1", &Regex::new(r"#\w+").unwrap());
+    }
+
     #[test]
     fn test_render_full_no_error_string_key() {
         let templ_str = "Something {data1} be {data2}, and { not here }";
@@ -523,4 +2341,1098 @@ mod test {
         let rendered = templ.render_string(&data).expect("Expected Result to be Ok");
         assert_eq!("Something should be here, and { not here }", rendered);
     }
+
+    #[test]
+    fn test_render_with_btreemap() {
+        let templ_str = "Something {data1} be {data2}, and { not here }";
+        let templ = Template::new(templ_str);
+        let data = {
+            let mut map = std::collections::BTreeMap::new();
+            map.insert("data1".to_string(), "should");
+            map.insert("data2".to_string(), "here");
+            map
+        };
+
+        let rendered = templ.render_with(&data).expect("Expected Result to be Ok");
+        assert_eq!("Something should be here, and { not here }", rendered);
+    }
+
+    #[test]
+    fn test_render_with_closure() {
+        let templ_str = "Something {data1} be {data2}, and { not here }";
+        let templ = Template::new(templ_str);
+        let data = |name: &str| {
+            return match name {
+                "data1" => Some("should"),
+                _ => None,
+            };
+        };
+
+        let rendered = templ.render_with(&data).expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::MissingData, rendered.kind());
+
+        let rendered = templ.render_nofail_with(&data);
+        assert_eq!("Something should be {data2}, and { not here }", rendered);
+    }
+
+    #[test]
+    fn test_render_with_dyn() {
+        let templ_str = "Something {data1} be {data2}, and { not here }";
+        let templ = Template::new(templ_str);
+        let data = vec![("data1", "should"), ("data2", "here")];
+        let source: &dyn ValueSource = &data;
+
+        let rendered = templ.render_with(source).expect("Expected Result to be Ok");
+        assert_eq!("Something should be here, and { not here }", rendered);
+        let rendered = templ.render_with(&source).expect("Expected Result to be Ok");
+        assert_eq!("Something should be here, and { not here }", rendered);
+    }
+
+    #[test]
+    fn test_render_positional() {
+        let templ = Template::new("{}, {{}}, { } and {}");
+        let rendered = templ
+            .render_positional(&[1.5, 2.0, 3.25])
+            .expect("Expected Result to be Ok");
+        assert_eq!("1.5, {}, 2 and 3.25", rendered);
+        // "{}" is rendered as-is by the other render functions
+        let rendered = templ
+            .render_with(&vec![("0", "zero")])
+            .expect("Expected Result to be Ok");
+        assert_eq!("{}, {}, { } and {}", rendered);
+
+        let templ = Template::new("{1}{#if 2}, {0 | upper}{/if}, {name:-default}");
+        let rendered = templ
+            .render_positional(&["a", "b", "c"])
+            .expect("Expected Result to be Ok");
+        assert_eq!("b, A, default", rendered);
+        let rendered = templ
+            .render_positional(&["a", "b", ""])
+            .expect("Expected Result to be Ok");
+        assert_eq!("b, default", rendered);
+
+        let err = Template::new("{0} {name}")
+            .render_positional(&["a"])
+            .expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::MissingData, err.kind());
+    }
+
+    #[test]
+    fn test_render_positional_errors() {
+        let err = Template::new("{} {}")
+            .render_positional(&["a"])
+            .expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::IndexOutOfRange, err.kind());
+        assert_eq!(Some((1, 4)), err.span().map(|v| return (v.line(), v.column())));
+
+        let err = Template::new("{0} {99999999999999999999999}")
+            .render_positional(&["a"])
+            .expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::IndexOutOfRange, err.kind());
+
+        let err = Template::new("{0} {1} {}")
+            .render_positional(&["a", "b", "c"])
+            .expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::MixedPositional, err.kind());
+        assert_eq!(Some((1, 9)), err.span().map(|v| return (v.line(), v.column())));
+    }
+
+    #[test]
+    fn test_format_spec() {
+        let templ =
+            Template::new("[{name:>6}] [{name:*^7}] {price:.2} {id:#06x} {count:+} {ratio:.1e} {missing:>4:-n/a}")
+                .with_format_specs();
+        let data = Value::from_pairs(vec![
+            ("name", Value::from("abc")),
+            ("price", Value::Float(4.5)),
+            ("id", Value::Integer(255)),
+            ("count", Value::Integer(3)),
+            ("ratio", Value::Float(1234.5)),
+        ]);
+
+        let rendered = templ.render_with(&data).expect("Expected Result to be Ok");
+        assert_eq!("[   abc] [**abc**] 4.50 0x00ff +3 1.2e3  n/a", rendered);
+
+        // typed positional values can be numbers
+        let rendered = Template::new("{0:.2} {1:05} {0:>6}")
+            .with_format_specs()
+            .render_positional_values(&[Value::from(1.0 / 3.0), Value::from(42)])
+            .expect("Expected Result to be Ok");
+        assert_eq!("0.33 00042 0.3333333333333333", rendered);
+
+        // "{a:}" is the same as "{a}", and a spec is not confused with a default value
+        let rendered = Template::new("{a:} {a:-x} {b:-x}")
+            .with_format_specs()
+            .render_with(&vec![("a", "1")])
+            .expect("Expected Result to be Ok");
+        assert_eq!("1 1 x", rendered);
+    }
+
+    #[test]
+    fn test_format_spec_errors() {
+        let templ = Template::new("{id:08x}").with_format_specs();
+
+        // strings are never formatted as numbers
+        let err = templ
+            .render_with(&vec![("id", "255")])
+            .expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::NotANumber, err.kind());
+        assert_eq!(Some("id"), err.placeholder());
+        assert_eq!("{id:08x}", templ.render_nofail_with(&vec![("id", "255")]));
+
+        let err = templ
+            .render_with(&Value::from_pairs(vec![("id", Value::Float(1.5))]))
+            .expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::NotANumber, err.kind());
+
+        // without format specs a ":" is part of the name
+        let rendered = Template::new("{a:b} {k:5}")
+            .render_with(&vec![("a:b", "1"), ("k:5", "2")])
+            .expect("Expected Result to be Ok");
+        assert_eq!("1 2", rendered);
+
+        // with format specs a ":" followed by a valid spec is not part of the name
+        let templ = Template::new("{a:b}").with_format_specs();
+        let err = templ
+            .render_with(&vec![("a:b", "1")])
+            .expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::MissingData, err.kind());
+        assert_eq!(Some("a"), err.placeholder());
+        let err = templ
+            .render_with(&vec![("a", "1")])
+            .expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::NotANumber, err.kind());
+        let rendered = Template::new("{k:5}|")
+            .with_format_specs()
+            .render_with(&vec![("k", "1"), ("k:5", "x")])
+            .expect("Expected Result to be Ok");
+        assert_eq!("1    |", rendered);
+
+        // precision is only supported for numbers, instead of truncating strings
+        let err = Template::new("{name:.2}")
+            .with_format_specs()
+            .render_with(&vec![("name", "abc")])
+            .expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::NotANumber, err.kind());
+
+        // the values of render_positional are never numbers, even if they are displayed as one
+        let templ = Template::new("{0:>5}").with_format_specs();
+        let rendered = templ.render_positional(&["nan"]).expect("Expected Result to be Ok");
+        assert_eq!("  nan", rendered);
+        let err = Template::new("{0:.2}")
+            .with_format_specs()
+            .render_positional(&[1.5])
+            .expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::NotANumber, err.kind());
+
+        // filters always return strings
+        let err = Template::new("{id | trim:x}")
+            .with_format_specs()
+            .render_with(&Value::from_pairs(vec![("id", Value::Integer(1))]))
+            .expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::NotANumber, err.kind());
+
+        // bound values are never numbers, so numeric specs are rejected when binding
+        let templ = Template::new("{name:>5}|{id:x}").with_format_specs();
+        let err = templ.bind(&["name", "id"]).expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::InvalidSyntax, err.kind());
+        assert_eq!(Some("id"), err.placeholder());
+
+        let templ = Template::new("{name:>5}|").with_format_specs();
+        let bound = templ.bind(&["name"]).expect("Expected Result to be Ok");
+        assert_eq!("    a|", bound.render(&["a"]).expect("Expected Result to be Ok"));
+    }
+
+    #[test]
+    fn test_bind() {
+        let templ = Template::new("{{{level}}} {message | upper}: {message} ({code:-none})");
+        let bound = templ.bind(&["message", "level"]).expect("Expected Result to be Ok");
+
+        let rendered = bound.render(&["disk full", "error"]).expect("Expected Result to be Ok");
+        assert_eq!("{error} DISK FULL: disk full (none)", rendered);
+        assert_eq!(rendered.len(), rendered.capacity());
+
+        let values = vec!["a".to_string(), "b".to_string()];
+        let rendered = bound.render(&values).expect("Expected Result to be Ok");
+        assert_eq!("{b} A: a (none)", rendered);
+
+        let err = bound.render(&["disk full"]).expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::MissingData, err.kind());
+    }
+
+    #[test]
+    fn test_bind_errors() {
+        let templ = Template::new("{level}: {message}");
+        let err = templ.bind(&["level"]).expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::MissingData, err.kind());
+        assert_eq!(Some("message"), err.placeholder());
+
+        let templ = Template::new("{level}{#if message}: {message}{/if}");
+        let err = templ
+            .bind(&["level", "message"])
+            .expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::InvalidSyntax, err.kind());
+
+        let templ = Template::new("{message | truncate(x)}");
+        let bound = templ.bind(&["message"]).expect("Expected Result to be Ok");
+        let err = bound.render(&["disk full"]).expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::Filter, err.kind());
+    }
+
+    #[test]
+    fn test_render_exact_capacity() {
+        let templ = Template::new("Something {data1} be {data2 | upper}, {{ and {data3:-default}");
+        let data = vec![("data1", "should"), ("data2", "here")];
+
+        let rendered = templ.render_with(&data).expect("Expected Result to be Ok");
+        assert_eq!("Something should be HERE, { and default", rendered);
+        assert_eq!(rendered.len(), rendered.capacity());
+
+        let rendered = templ.render_nofail_with(&vec![("data1", "should")]);
+        assert_eq!("Something should be {data2 | upper}, { and default", rendered);
+        assert_eq!(rendered.len(), rendered.capacity());
+    }
+
+    #[test]
+    fn test_render_single_lookup() {
+        let lookups = std::cell::RefCell::new(Vec::new());
+        let values = |name: &str| {
+            lookups.borrow_mut().push(name.to_string());
+            return if name == "data1" { Some("should") } else { None };
+        };
+
+        let rendered = Template::new("{data1} {data2:-default} {data3 | upper:-other}")
+            .render_with(&values)
+            .expect("Expected Result to be Ok");
+        assert_eq!("should default OTHER", rendered);
+        assert_eq!(vec!["data1", "data2", "data3"], *lookups.borrow());
+    }
+
+    /// Get the content of `value` and whether it borrows instead of owning
+    fn cow_parts(value: Cow<'_, str>) -> (String, bool) {
+        return match value {
+            Cow::Borrowed(v) => (v.to_string(), true),
+            Cow::Owned(v) => (v, false),
+        };
+    }
+
+    #[test]
+    fn test_render_cow() {
+        let data = vec![("data1", "should"), ("data2", "{data2}")];
+        let render = |src: &str| {
+            return cow_parts(Template::new(src).render_cow(&data).expect("Expected Result to be Ok"));
+        };
+
+        assert_eq!(("Nothing here".to_string(), true), render("Nothing here"));
+        // a value that is the same as its placeholder does not change the output
+        assert_eq!(("Something {data2}".to_string(), true), render("Something {data2}"));
+        assert_eq!(
+            ("Something should be {data2}".to_string(), false),
+            render("Something {data1} be {data2}")
+        );
+        // escapes change the output
+        assert_eq!(("Something {data1}".to_string(), false), render("Something {{data1}}"));
+
+        let err = Template::new("Something {data3}")
+            .render_cow(&data)
+            .expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::MissingData, err.kind());
+    }
+
+    #[test]
+    fn test_render_nofail_cow() {
+        let templ = Template::new("Something {data1} be {data2}{#if data1}, if{/if}");
+
+        // the skipped section is at the end, so the output is still the beginning of the template string
+        assert_eq!(
+            ("Something {data1} be {data2}".to_string(), true),
+            cow_parts(templ.render_nofail_cow(&vec![("data3", "unused")]))
+        );
+        assert_eq!(
+            ("Something should be {data2}, if".to_string(), false),
+            cow_parts(templ.render_nofail_cow(&vec![("data1", "should")]))
+        );
+    }
+
+    #[test]
+    fn test_render_to_fmt() {
+        let templ_str = "Something {data1} be {data2}, and { not here }";
+        let templ = Template::new(templ_str);
+        let data = vec![("data1", "should"), ("data2", "here")];
+
+        let mut out = String::from("start: ");
+        templ.render_to_fmt(&mut out, &data).expect("Expected Result to be Ok");
+        assert_eq!("start: Something should be here, and { not here }", out);
+    }
+
+    #[test]
+    fn test_render_to_fmt_missing_data() {
+        let templ_str = "Something {data1} be {data2}, and { not here }";
+        let templ = Template::new(templ_str);
+        let data = vec![("data1", "should")];
+
+        let mut out = String::new();
+        let err = templ
+            .render_to_fmt(&mut out, &data)
+            .expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::MissingData, err.kind());
+        assert_eq!("Something should be ", out);
+    }
+
+    #[test]
+    fn test_render_to_io() {
+        let templ_str = "Something {data1} be {data2}, and { not here }";
+        let templ = Template::new(templ_str);
+        let data = vec![("data1", "should"), ("data2", "here")];
+
+        let mut out: Vec<u8> = Vec::new();
+        templ.render_to_io(&mut out, &data).expect("Expected Result to be Ok");
+        assert_eq!(b"Something should be here, and { not here }", out.as_slice());
+    }
+
+    #[test]
+    fn test_render_to_io_error() {
+        struct FailingWriter;
+
+        impl io::Write for FailingWriter {
+            fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+                return Err(io::Error::new(io::ErrorKind::BrokenPipe, "closed"));
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                return Ok(());
+            }
+        }
+
+        let templ = Template::new("Something {data1}");
+        let data = vec![("data1", "should")];
+
+        let err = templ
+            .render_to_io(&mut FailingWriter, &data)
+            .expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::Io, err.kind());
+        assert_eq!("Io: closed", err.to_string());
+    }
+
+    #[test]
+    fn test_render_missing_data_span() {
+        let templ_str = "Something {data1}\nbe {  data2 }, and { not here }";
+        let templ = Template::new(templ_str);
+        let data = vec![("data1", "should")];
+
+        let err = templ.render_with(&data).expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::MissingData, err.kind());
+        assert_eq!(Some("data2"), err.placeholder());
+
+        let span = err.span().expect("Expected a Span");
+        assert_eq!("{  data2 }", &templ_str[span.range()]);
+        assert_eq!((2, 4), (span.line(), span.column()));
+        assert_eq!(
+            "MissingData: Missing Data for Argument \"data2\" at 2:4",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let templ_str = "Something {data1} be {data2},\nand {data3} {data2}";
+        let templ = Template::new(templ_str);
+        let data = vec![("data1", "should")];
+
+        let err = templ.check(&data).expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::Multiple, err.kind());
+        assert_eq!(
+            "Multiple: Missing Data for 3 Argument(s): \"data2\" at 1:22, \"data3\" at 2:5, \"data2\" at 2:13",
+            err.to_string()
+        );
+
+        let missing: Vec<_> = err
+            .errors()
+            .iter()
+            .map(|v| return (v.kind(), v.placeholder(), v.span().map(|v| return v.start())))
+            .collect();
+        assert_eq!(
+            vec![
+                (TemplateErrorKind::MissingData, Some("data2"), Some(21)),
+                (TemplateErrorKind::MissingData, Some("data3"), Some(34)),
+                (TemplateErrorKind::MissingData, Some("data2"), Some(42)),
+            ],
+            missing
+        );
+
+        let data = vec![("data1", "should"), ("data2", "here"), ("data3", "too")];
+        templ.check(&data).expect("Expected Result to be Ok");
+    }
+
+    #[test]
+    fn test_placeholders() {
+        let templ_str = "Something {data1} be {data2},\nand { data1 }{ not here }";
+        let templ = Template::new(templ_str);
+
+        let found: Vec<_> = templ
+            .placeholders()
+            .map(|v| return (v.name(), &templ_str[v.span().range()], v.span().line(), v.count()))
+            .collect();
+        assert_eq!(
+            vec![
+                ("data1", "{data1}", 1, 2),
+                ("data2", "{data2}", 1, 1),
+                ("data1", "{ data1 }", 2, 2)
+            ],
+            found
+        );
+    }
+
+    #[test]
+    fn test_placeholders_empty() {
+        let templ = Template::new("No Data here");
+
+        assert_eq!(0, templ.placeholders().count());
+        assert!(templ.required_keys().is_empty());
+    }
+
+    #[test]
+    fn test_placeholders_repeated() {
+        let templ = Template::try_new("{title}{#each jobs}{name}{#if ok}{ok}{/if}{.}{/each}{#if show}{footer}{/if}")
+            .expect("Expected Result to be Ok");
+
+        let found: Vec<_> = templ
+            .placeholders()
+            .map(|v| return (v.name(), v.is_repeated(), v.is_section(), v.count()))
+            .collect();
+        assert_eq!(
+            vec![
+                ("title", false, false, 1),
+                ("jobs", false, true, 1),
+                ("name", true, false, 1),
+                ("ok", true, true, 2),
+                ("ok", true, false, 2),
+                (".", true, false, 1),
+                ("show", false, true, 1),
+                ("footer", false, false, 1)
+            ],
+            found
+        );
+
+        let keys: Vec<&str> = templ.required_keys().into_iter().collect();
+        assert_eq!(vec!["footer", "jobs", "show", "title"], keys);
+    }
+
+    #[test]
+    fn test_required_keys() {
+        let templ = Template::new("{data3} Something {data1} be {data2}, and {data1}");
+
+        let keys: Vec<&str> = templ.required_keys().into_iter().collect();
+        assert_eq!(vec!["data1", "data2", "data3"], keys);
+    }
+
+    #[test]
+    fn test_default_escapes() {
+        let templ_str = "Something {{data1}} be {data2}, and {{{data1}}} {{ not here }}";
+        let templ = Template::new(templ_str);
+        let data = {
+            let mut map = HashMap::new();
+            map.insert("data1", "should");
+            map.insert("data2", "here");
+            map
+        };
+
+        let rendered = templ.render(&data).expect("Expected Result to be Ok");
+        assert_eq!("Something {data1} be here, and {should} { not here }", rendered);
+
+        let rendered = templ.render_nofail(&HashMap::<&str, &str>::new());
+        assert_eq!("Something {data1} be {data2}, and {{data1}} { not here }", rendered);
+
+        let keys: Vec<&str> = templ.required_keys().into_iter().collect();
+        assert_eq!(vec!["data1", "data2"], keys);
+    }
+
+    #[test]
+    fn test_default_escapes_json() {
+        let templ_str = r#"{{"name": "{name}", "nested": {{"id": {id}}}}}"#;
+        let templ = Template::new(templ_str);
+        let data = vec![("name", "something"), ("id", "10")];
+
+        let rendered = templ.render_with(&data).expect("Expected Result to be Ok");
+        assert_eq!(r#"{"name": "something", "nested": {"id": 10}}"#, rendered);
+    }
+
+    #[cfg(feature = "custom-regex")]
+    #[test]
+    fn test_custom_regex_no_escapes() {
+        let custom_regex = Regex::new(r"(?mi)#(\S+)").unwrap();
+        let templ = Template::new("{{#data1}}").with_regex(&custom_regex);
+        let data = vec![("data1}}", "can be seen")];
+
+        let rendered = templ.render_with(&data).expect("Expected Result to be Ok");
+        assert_eq!("{{can be seen", rendered);
+    }
+
+    #[test]
+    fn test_default_value() {
+        let templ_str = "Hello {user:-anonymous user}, {greeting :- nice to see you }{ end:-}";
+        let templ = Template::new(templ_str);
+
+        let rendered = templ
+            .render_with(&vec![("greeting", "welcome")])
+            .expect("Expected Result to be Ok");
+        assert_eq!("Hello anonymous user, welcome", rendered);
+
+        let rendered = templ
+            .render_with(&vec![("user", "someone"), ("end", "!")])
+            .expect("Expected Result to be Ok");
+        assert_eq!("Hello someone, nice to see you!", rendered);
+
+        templ
+            .check(&Vec::<(&str, &str)>::new())
+            .expect("Expected Result to be Ok");
+        assert!(templ.required_keys().is_empty());
+
+        let found: Vec<_> = templ
+            .placeholders()
+            .map(|v| return (v.name(), v.default_value()))
+            .collect();
+        assert_eq!(
+            vec![
+                ("user", Some("anonymous user")),
+                ("greeting", Some("nice to see you")),
+                ("end", Some(""))
+            ],
+            found
+        );
+    }
+
+    #[test]
+    fn test_default_value_mixed() {
+        let templ = Template::new("{data1:-default} and {data1}");
+
+        let err = templ
+            .render_with(&Vec::<(&str, &str)>::new())
+            .expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::MissingData, err.kind());
+        assert_eq!(
+            "default and {data1}",
+            templ.render_nofail_with(&Vec::<(&str, &str)>::new())
+        );
+
+        let keys: Vec<&str> = templ.required_keys().into_iter().collect();
+        assert_eq!(vec!["data1"], keys);
+    }
+
+    #[cfg(feature = "custom-regex")]
+    #[test]
+    fn test_custom_regex_no_default() {
+        let custom_regex = Regex::new(r"(?mi)\{\{\s*(\S+?)\s*(?::-(\S*))?\s*\}\}").unwrap();
+        let templ = Template::new("Something {{data1:-default}}").with_regex(&custom_regex);
+
+        assert_eq!(
+            "Something {{data1:-default}}",
+            templ.render_nofail_with(&Vec::<(&str, &str)>::new())
+        );
+    }
+
+    #[test]
+    fn test_filters() {
+        let templ_str =
+            "Something {data1|upper} be { data2 | trim | pad_left(6, \"-\") }, and {data3 | truncate(3) :- default}";
+        let templ = Template::new(templ_str);
+        let data = vec![("data1", "should"), ("data2", " here ")];
+
+        let rendered = templ.render_with(&data).expect("Expected Result to be Ok");
+        assert_eq!("Something SHOULD be --here, and def", rendered);
+
+        let keys: Vec<&str> = templ.required_keys().into_iter().collect();
+        assert_eq!(vec!["data1", "data2"], keys);
+
+        // separators inside quotes are part of the argument
+        let templ = Template::new("{a | pad_left(3, \",\")} {a | pad_right(3, \"|\")} {a | pad_left(4, \"ab\")}");
+        let rendered = templ.render_with(&vec![("a", "x")]).expect("Expected Result to be Ok");
+        assert_eq!(",,x x|| abax", rendered);
+
+        // a single digit can be used as fill without quotes
+        let templ = Template::new("{a | pad_left(3, 0)}");
+        let rendered = templ.render_with(&vec![("a", "7")]).expect("Expected Result to be Ok");
+        assert_eq!("007", rendered);
+    }
+
+    #[test]
+    fn test_filters_errors() {
+        let templ_str = "Something {data1 | unknown} be {data2 | truncate(many)}";
+        let templ = Template::new(templ_str);
+        let data = vec![("data1", "should"), ("data2", "here")];
+
+        let err = templ.render_with(&data).expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::UnknownFilter, err.kind());
+        assert_eq!(Some("data1"), err.placeholder());
+        assert_eq!("UnknownFilter: Unknown Filter \"unknown\" at 1:11", err.to_string());
+
+        let templ = Template::new("Something {data2 | truncate(many)}");
+        let err = templ.render_with(&data).expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::Filter, err.kind());
+        assert_eq!(
+            "Filter: Filter \"truncate\" failed: Expected first argument to be a positive integer at 1:11",
+            err.to_string()
+        );
+
+        let templ = Template::new(templ_str);
+        assert_eq!(
+            "Something {data1 | unknown} be {data2 | truncate(many)}",
+            templ.render_nofail_with(&data)
+        );
+    }
+
+    #[test]
+    fn test_register_filter() {
+        let mut templ = Template::new("Something {data1 | upper | wrap(\"<\", \">\")}");
+        templ.register_filter("upper", |value: &str, _: &[FilterArg]| {
+            return Ok(format!("custom {}", value));
+        });
+        templ.register_filter("wrap", |value: &str, args: &[FilterArg]| {
+            let start = args.first().and_then(FilterArg::as_str).unwrap_or_default();
+            let end = args.get(1).and_then(FilterArg::as_str).unwrap_or_default();
+            return Ok(format!("{}{}{}", start, value, end));
+        });
+
+        let rendered = templ
+            .render_with(&vec![("data1", "should")])
+            .expect("Expected Result to be Ok");
+        assert_eq!("Something <custom should>", rendered);
+
+        let cloned = templ.clone();
+        assert_eq!(templ, cloned);
+    }
+
+    #[cfg(feature = "custom-regex")]
+    #[test]
+    fn test_filters_not_for_custom_regex() {
+        let custom_regex = Regex::new(r"(?mi)\{\{(.+?)\}\}").unwrap();
+        let templ = Template::new("Something {{data1|upper}}").with_regex(&custom_regex);
+        let data = vec![("data1|upper", "should")];
+
+        let rendered = templ.render_with(&data).expect("Expected Result to be Ok");
+        assert_eq!("Something should", rendered);
+    }
+
+    #[test]
+    fn test_sections() {
+        let templ_str = "Mail to {to}{#if cc}, cc: {cc}{#else} (no cc){/if}{ #if bcc }, bcc: {bcc}{ /if }!";
+        let templ = Template::try_new(templ_str).expect("Expected Result to be Ok");
+
+        let rendered = templ
+            .render_with(&vec![("to", "someone")])
+            .expect("Expected Result to be Ok");
+        assert_eq!("Mail to someone (no cc)!", rendered);
+
+        let rendered = templ
+            .render_with(&vec![("to", "someone"), ("cc", "other"), ("bcc", "")])
+            .expect("Expected Result to be Ok");
+        assert_eq!("Mail to someone, cc: other!", rendered);
+
+        let rendered = templ
+            .render_with(&vec![("to", "someone"), ("bcc", "hidden")])
+            .expect("Expected Result to be Ok");
+        assert_eq!("Mail to someone (no cc), bcc: hidden!", rendered);
+
+        // the flat introspection api still works with sections
+        let keys: Vec<&str> = templ.required_keys().into_iter().collect();
+        assert_eq!(vec!["bcc", "cc", "to"], keys);
+    }
+
+    #[test]
+    fn test_sections_nested() {
+        let templ =
+            Template::try_new("{#if a}a{#if b}b{#else}!b{/if}{#else}!a{/if}").expect("Expected Result to be Ok");
+
+        assert_eq!("ab", templ.render_nofail_with(&vec![("a", "1"), ("b", "1")]));
+        assert_eq!("a!b", templ.render_nofail_with(&vec![("a", "1")]));
+        assert_eq!("!a", templ.render_nofail_with(&vec![("b", "1")]));
+    }
+
+    #[test]
+    fn test_sections_check() {
+        let templ = Template::try_new("{#if cc}cc: {cc} {name}{#else}{to}{/if}").expect("Expected Result to be Ok");
+
+        let err = templ
+            .check(&vec![("cc", "other")])
+            .expect_err("Expected Result to be Err");
+        let missing: Vec<_> = err.errors().iter().filter_map(|v| return v.placeholder()).collect();
+        assert_eq!(vec!["name"], missing);
+
+        let err = templ
+            .check(&Vec::<(&str, &str)>::new())
+            .expect_err("Expected Result to be Err");
+        let missing: Vec<_> = err.errors().iter().filter_map(|v| return v.placeholder()).collect();
+        assert_eq!(vec!["to"], missing);
+    }
+
+    #[test]
+    fn test_sections_invalid() {
+        let cases = [
+            ("{#if a}unclosed", "Found \"{#if}\" without \"{/if}\" at 1:1"),
+            ("text {/if}", "Found \"{/if}\" without \"{#if}\" at 1:6"),
+            ("{#else}", "Found \"{#else}\" outside of a section at 1:1"),
+            ("{#if a}{#else}{#else}{/if}", "Duplicate \"{#else}\" in section at 1:15"),
+            ("{#each a}unclosed", "Found \"{#each}\" without \"{/each}\" at 1:1"),
+            ("{/each}", "Found \"{/each}\" without \"{#each}\" at 1:1"),
+            ("{#if a}{/each}", "Found \"{/each}\" but expected \"{/if}\" at 1:8"),
+        ];
+
+        for (templ_str, message) in &cases {
+            let err = Template::try_new(*templ_str).expect_err("Expected Result to be Err");
+            assert_eq!(TemplateErrorKind::InvalidSyntax, err.kind());
+            assert_eq!(format!("InvalidSyntax: {}", message), err.to_string());
+        }
+
+        // "new" falls back to treating the sections as text
+        let templ = Template::new("{#if a}unclosed {a}");
+        assert_eq!("{#if a}unclosed 1", templ.render_nofail_with(&vec![("a", "1")]));
+    }
+
+    #[test]
+    fn test_each() {
+        let templ_str = "Failed jobs in {project}:{#each jobs}\n- {name} ({project}){#else} none{/each}";
+        let templ = Template::try_new(templ_str).expect("Expected Result to be Ok");
+        let data = Value::from_pairs(vec![
+            ("project", Value::from("crate")),
+            (
+                "jobs",
+                Value::from(vec![
+                    Value::from_pairs(vec![("name", "build")]),
+                    Value::from_pairs(vec![("name", "test"), ("project", "other")]),
+                ]),
+            ),
+        ]);
+
+        let rendered = templ.render_with(&data).expect("Expected Result to be Ok");
+        assert_eq!("Failed jobs in crate:\n- build (crate)\n- test (other)", rendered);
+
+        let data = Value::from_pairs(vec![("project", Value::from("crate")), ("jobs", Value::List(vec![]))]);
+        let rendered = templ.render_with(&data).expect("Expected Result to be Ok");
+        assert_eq!("Failed jobs in crate: none", rendered);
+
+        // a missing list is a error, only rendering without failing uses the else branch
+        let data = vec![("project", "crate")];
+        let err = templ.render_with(&data).expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::MissingData, err.kind());
+        assert_eq!(Some("jobs"), err.placeholder());
+        assert_eq!("Failed jobs in crate: none", templ.render_nofail_with(&data));
+
+        let err = templ.check(&data).expect_err("Expected Result to be Err");
+        let missing: Vec<_> = err
+            .errors()
+            .iter()
+            .map(|v| return (v.kind(), v.placeholder()))
+            .collect();
+        assert_eq!(vec![(TemplateErrorKind::MissingData, Some("jobs"))], missing);
+
+        // a value that is not a list is a error, too
+        let data = vec![("project", "crate"), ("jobs", "build")];
+        let err = templ.render_with(&data).expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::NotAList, err.kind());
+        assert_eq!(
+            "NotAList: Invalid Value for Section \"jobs\": it is not a list at 1:26",
+            err.to_string()
+        );
+        let err = templ.check(&data).expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::NotAList, err.errors()[0].kind());
+
+        // errors in the path of the list are reported like for placeholders
+        let templ = Template::try_new("{#each user.jobs}{name}{/each}{#each user.name.tags}{.}{/each}")
+            .expect("Expected Result to be Ok");
+        let err = templ
+            .render_with(&Value::from_pairs(vec![("jobs", vec!["a"])]))
+            .expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::MissingData, err.kind());
+        assert_eq!(Some("user.jobs"), err.placeholder());
+
+        let data = Value::from_pairs(vec![(
+            "user",
+            Value::from_pairs(vec![("jobs", Value::List(vec![])), ("name", Value::from("some"))]),
+        )]);
+        let err = templ.render_with(&data).expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::NotAContainer, err.kind());
+        let err = templ.check(&data).expect_err("Expected Result to be Err");
+        let kinds: Vec<_> = err.errors().iter().map(TemplateError::kind).collect();
+        assert_eq!(vec![TemplateErrorKind::NotAContainer], kinds);
+    }
+
+    #[test]
+    fn test_each_separator() {
+        let templ =
+            Template::try_new("Tags: {#each tags sep=\", \"}{. | upper}{/each}.").expect("Expected Result to be Ok");
+        let data = Value::from_pairs(vec![("tags", vec!["a", "b", "c"])]);
+
+        let rendered = templ.render_with(&data).expect("Expected Result to be Ok");
+        assert_eq!("Tags: A, B, C.", rendered);
+    }
+
+    #[test]
+    fn test_each_check() {
+        let templ = Template::try_new("{#each jobs}{name} {status}{/each}").expect("Expected Result to be Ok");
+        let data = Value::from_pairs(vec![(
+            "jobs",
+            Value::from(vec![
+                Value::from_pairs(vec![("name", "build"), ("status", "ok")]),
+                Value::from_pairs(vec![("name", "test")]),
+            ]),
+        )]);
+
+        let err = templ.check(&data).expect_err("Expected Result to be Err");
+        let missing: Vec<_> = err.errors().iter().filter_map(|v| return v.placeholder()).collect();
+        assert_eq!(vec!["status"], missing);
+
+        let err = templ.render_with(&data).expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::MissingData, err.kind());
+        assert_eq!(
+            "build ok{name} {status}",
+            templ.render_nofail_with(&Value::from_pairs(vec![(
+                "jobs",
+                Value::from(vec![
+                    Value::from_pairs(vec![("name", "build"), ("status", "ok")]),
+                    Value::Map(Default::default())
+                ]),
+            )]))
+        );
+    }
+
+    #[test]
+    fn test_paths() {
+        let templ = Template::new("{user.address.city} {jobs[1].name} {jobs[0].tags[1]} {user.name}");
+        let data = Value::from_pairs(vec![
+            (
+                "user",
+                Value::from_pairs(vec![
+                    ("name", Value::from("some")),
+                    ("address", Value::from_pairs(vec![("city", "Berlin")])),
+                ]),
+            ),
+            (
+                "jobs",
+                Value::from(vec![
+                    Value::from_pairs(vec![
+                        ("name", Value::from("build")),
+                        ("tags", Value::from(vec!["a", "b"])),
+                    ]),
+                    Value::from_pairs(vec![("name", "test")]),
+                ]),
+            ),
+        ]);
+
+        let rendered = templ.render_with(&data).expect("Expected Result to be Ok");
+        assert_eq!("Berlin test b some", rendered);
+
+        // flat keys are still used first
+        let rendered = Template::new("{user.name}")
+            .render_with(&vec![("user.name", "flat")])
+            .expect("Expected Result to be Ok");
+        assert_eq!("flat", rendered);
+
+        // paths also work for sections
+        let templ = Template::try_new("{#if user.name}{#each jobs[0].tags sep=\",\"}{.}{/each}{/if}")
+            .expect("Expected Result to be Ok");
+        assert_eq!("a,b", templ.render_with(&data).expect("Expected Result to be Ok"));
+    }
+
+    #[test]
+    fn test_paths_errors() {
+        let data = Value::from_pairs(vec![
+            ("user", Value::from_pairs(vec![("name", "some")])),
+            ("jobs", Value::from(vec!["build"])),
+        ]);
+
+        let err = Template::new("{user.address.city}")
+            .render_with(&data)
+            .expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::MissingData, err.kind());
+        assert_eq!(
+            "MissingData: Missing Data for Argument \"user.address.city\": \"user.address\" does not exist at 1:1",
+            err.to_string()
+        );
+
+        let err = Template::new("{jobs[3]}")
+            .render_with(&data)
+            .expect_err("Expected Result to be Err");
+        assert_eq!(
+            "MissingData: Missing Data for Argument \"jobs[3]\": \"jobs[3]\" does not exist at 1:1",
+            err.to_string()
+        );
+
+        let err = Template::new("{user.name.first}")
+            .render_with(&data)
+            .expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::NotAContainer, err.kind());
+        assert_eq!(
+            "NotAContainer: Invalid Path \"user.name.first\": \"user.name\" is not a map or list at 1:1",
+            err.to_string()
+        );
+
+        let err = Template::new("{user[0]} {jobs.name}")
+            .check(&data)
+            .expect_err("Expected Result to be Err");
+        let kinds: Vec<_> = err.errors().iter().map(|v| return v.kind()).collect();
+        assert_eq!(
+            vec![TemplateErrorKind::NotAContainer, TemplateErrorKind::NotAContainer],
+            kinds
+        );
+        assert_eq!(
+            "Multiple: Missing or Invalid Data for 2 Argument(s): \"user[0]\" at 1:1, \"jobs.name\" at 1:11",
+            err.to_string()
+        );
+
+        // defaults are only used for missing values
+        let templ = Template::new("{user.address.city:-none} {user.name.first:-none}");
+        assert_eq!("none {user.name.first:-none}", templ.render_nofail_with(&data));
+    }
+
+    #[test]
+    fn test_not_a_scalar() {
+        let data = Value::from_pairs(vec![
+            ("user", Value::from_pairs(vec![("name", "some")])),
+            ("jobs", Value::from(vec!["build"])),
+        ]);
+
+        for (templ_str, expected) in &[
+            (
+                "{user}",
+                "NotAScalar: Invalid Value for Argument \"user\": it is a map or list at 1:1",
+            ),
+            (
+                "{jobs}",
+                "NotAScalar: Invalid Value for Argument \"jobs\": it is a map or list at 1:1",
+            ),
+        ] {
+            let err = Template::new(*templ_str)
+                .render_with(&data)
+                .expect_err("Expected Result to be Err");
+            assert_eq!(TemplateErrorKind::NotAScalar, err.kind());
+            assert_eq!(*expected, err.to_string());
+        }
+
+        // "Null" is a missing value
+        let data = Value::from_pairs(vec![("nick", Value::Null)]);
+        assert_eq!("none", Template::new("{nick:-none}").render_nofail_with(&data));
+    }
+
+    #[test]
+    fn test_render_values() {
+        let templ = Template::try_new(
+            "{user.name} ({age:03}, {score:.1}){#if admin} admin{/if}{#if active} active{/if} {nick:-none}",
+        )
+        .expect("Expected Result to be Ok")
+        .with_format_specs();
+        let mut data = HashMap::new();
+        data.insert("user", Value::from_pairs(vec![("name", "alice")]));
+        data.insert("age", Value::from(7u8));
+        data.insert("score", Value::from(9.25));
+        data.insert("admin", Value::from(false));
+        data.insert("active", Value::from(true));
+        data.insert("nick", Value::from(None::<String>));
+
+        let rendered = templ.render_values(&data).expect("Expected Result to be Ok");
+        assert_eq!("alice (007, 9.2) active none", rendered);
+
+        // the string "false" is not empty and a map is always true
+        let rendered = templ
+            .render_with(&Value::from_pairs(vec![
+                ("user", Value::from_pairs(vec![("name", "bob")])),
+                ("age", Value::from(1)),
+                ("score", Value::from(1.0)),
+                ("admin", Value::from("false")),
+                ("active", Value::from_pairs(vec![("flag", false)])),
+            ]))
+            .expect("Expected Result to be Ok");
+        assert_eq!("bob (001, 1.0) admin active none", rendered);
+
+        // lists are true if they are not empty
+        let templ =
+            Template::try_new("{#if items}{#each items sep=\",\"}{.}{/each}{#else}none{/if}{#if user.tags} tags{/if}")
+                .expect("Expected Result to be Ok");
+        let mut data = HashMap::new();
+        data.insert("items", Value::from(vec![1, 2]));
+        data.insert(
+            "user",
+            Value::from_pairs(vec![("tags", Value::from(Vec::<String>::new()))]),
+        );
+        assert_eq!("1,2", templ.render_values(&data).expect("Expected Result to be Ok"));
+
+        data.insert("items", Value::from(Vec::<i64>::new()));
+        data.insert("user", Value::from_pairs(vec![("tags", vec!["a"])]));
+        assert_eq!(
+            "none tags",
+            templ.render_values(&data).expect("Expected Result to be Ok")
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_render_serialize() {
+        #[derive(serde::Serialize)]
+        struct User {
+            name:  String,
+            admin: bool,
+            score: f64,
+            nick:  Option<String>,
+            tags:  Vec<&'static str>,
+        }
+
+        let user = User {
+            name:  "some".into(),
+            admin: true,
+            score: 1.5,
+            nick:  None,
+            tags:  vec!["a", "b"],
+        };
+
+        let templ = Template::try_new("{name} {admin} {score} {nick:-none} {tags[1]} {#each tags sep=\",\"}{.}{/each}")
+            .expect("Expected Result to be Ok");
+        assert_eq!(
+            "some true 1.5 none b a,b",
+            templ.render_serialize(&user).expect("Expected Result to be Ok")
+        );
+
+        let err = Template::new("{tags}")
+            .render_serialize(&user)
+            .expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::NotAScalar, err.kind());
+
+        let mut map = std::collections::BTreeMap::new();
+        map.insert(vec![1], "a");
+        let err = Template::new("{tags}")
+            .render_serialize(&map)
+            .expect_err("Expected Result to be Err");
+        assert_eq!(TemplateErrorKind::Serialize, err.kind());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_template() {
+        use serde::{
+            de::{
+                value::{
+                    Error,
+                    StrDeserializer,
+                },
+                IntoDeserializer,
+            },
+            Deserialize,
+        };
+
+        let deserializer: StrDeserializer<'_, Error> = "Hello {#if name}{name}{/if}".into_deserializer();
+        let templ = Template::deserialize(deserializer).expect("Expected Result to be Ok");
+        assert_eq!(
+            Template::try_new("Hello {#if name}{name}{/if}").expect("Expected Result to be Ok"),
+            templ
+        );
+        assert_eq!(
+            Value::from("Hello {#if name}{name}{/if}"),
+            crate::serialize::to_value(&templ).expect("Expected Result to be Ok")
+        );
+
+        let deserializer: StrDeserializer<'_, Error> = "Hello {#if name}".into_deserializer();
+        let err = Template::deserialize(deserializer).expect_err("Expected Result to be Err");
+        assert_eq!(
+            "InvalidSyntax: Found \"{#if}\" without \"{/if}\" at 1:7",
+            err.to_string()
+        );
+    }
 }
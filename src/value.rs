@@ -0,0 +1,470 @@
+//! Module for [`Value`], a nested value that can be used to render a [`Template`](crate::template::Template)
+
+use std::{
+    borrow::{
+        Borrow,
+        Cow,
+    },
+    collections::{
+        BTreeMap,
+        HashMap,
+    },
+    convert::TryFrom,
+    fmt,
+    hash::{
+        BuildHasher,
+        Hash,
+    },
+};
+
+#[cfg(feature = "serde")]
+use crate::error::{
+    TemplateError,
+    TemplateErrorKind,
+};
+use crate::source::{
+    explicit_index,
+    Positional,
+    ValueSource,
+};
+
+/// A nested value, which can be used to render sections like `{#each items}...{/each}`
+///
+/// As a [`ValueSource`], a [`Value::Map`] provides its entries, the special name `.` provides a scalar value itself.  
+/// Scalar values are rendered as their string representation, except [`Value::Null`] which is treated as a missing value.  
+/// Nested values can be accessed with paths like `{user.address.city}` or `{jobs[0].name}`.  
+/// Values can be created with [`Value::from`] for strings, integers, floats, booleans, [`Option`]s, [`Vec`]s and maps,
+/// numbers keep their type for numeric format specs like `{price:.2}` and booleans are used as-is for `{#if}` sections.
+/// # Example
+/// ```rust
+/// # use new_string_template::template::Template;
+/// # use new_string_template::value::Value;
+/// let templ = Template::try_new("Failed:{#each jobs}\n- {name} ({#each tags sep=\", \"}{.}{/each}){/each}").unwrap();
+/// let data = Value::from_pairs(vec![(
+///     "jobs",
+///     Value::from(vec![
+///         Value::from_pairs(vec![("name", Value::from("build")), ("tags", Value::from(vec!["linux", "arm"]))]),
+///         Value::from_pairs(vec![("name", Value::from("test")), ("tags", Value::from(vec!["windows"]))]),
+///     ]),
+/// )]);
+///
+/// let rendered = templ.render_with(&data).expect("Expected Result to be Ok");
+/// assert_eq!("Failed:\n- build (linux, arm)\n- test (windows)", rendered);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// A missing value, like a [`None`]
+    Null,
+    /// A boolean value, rendered as `true` or `false`
+    Bool(bool),
+    /// A integer value
+    Integer(i64),
+    /// A floating point value
+    Float(f64),
+    /// A single string value
+    String(String),
+    /// A list of values, which can be used with `{#each}`
+    List(Vec<Value>),
+    /// A map of values, which provides its entries by name
+    Map(BTreeMap<String, Value>),
+}
+
+/// A typed number provided by a [`ValueSource`], used for numeric format specs like `{price:.2}` or `{id:08x}`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Number {
+    /// A integer number
+    Integer(i64),
+    /// A floating point number
+    Float(f64),
+}
+
+impl Value {
+    /// Create a new [`Value::Map`] from key-value pairs
+    pub fn from_pairs<K, V, I>(pairs: I) -> Self
+    where
+        K: Into<String>,
+        V: Into<Value>,
+        I: IntoIterator<Item = (K, V)>,
+    {
+        return Self::Map(pairs.into_iter().map(|(k, v)| return (k.into(), v.into())).collect());
+    }
+
+    /// Get the entry `name` of a [`Value::Map`]
+    fn entry(&self, name: &str) -> Option<&Value> {
+        return match self {
+            Self::Map(map) => map.get(name),
+            _ => None,
+        };
+    }
+
+    /// Get the entry `name`, or this value itself for the special name `.`
+    fn entry_or_self(&self, name: &str) -> Option<&Value> {
+        return if name == "." { Some(self) } else { self.entry(name) };
+    }
+
+    /// Get the string representation of a scalar value, [`None`] for [`Value::Null`], lists and maps
+    fn as_scalar(&self) -> Option<Cow<'_, str>> {
+        return match self {
+            Self::String(v) => Some(Cow::Borrowed(v)),
+            Self::Bool(v) => Some(Cow::Borrowed(if *v { "true" } else { "false" })),
+            Self::Integer(v) => Some(Cow::Owned(v.to_string())),
+            Self::Float(v) => Some(Cow::Owned(v.to_string())),
+            Self::Null | Self::List(_) | Self::Map(_) => None,
+        };
+    }
+
+    /// Get the items of a [`Value::List`]
+    fn as_list(&self) -> Option<Vec<&dyn ValueSource>> {
+        return match self {
+            Self::List(list) => Some(list.iter().map(|v| return v as &dyn ValueSource).collect()),
+            _ => None,
+        };
+    }
+
+    /// Get a [`Value::Map`] as nested source
+    fn as_child(&self) -> Option<&dyn ValueSource> {
+        return match self {
+            Self::Map(_) => Some(self),
+            _ => None,
+        };
+    }
+
+    /// Get a [`Value::Integer`] or [`Value::Float`] as [`Number`]
+    fn as_number(&self) -> Option<Number> {
+        return match self {
+            Self::Integer(v) => Some(Number::Integer(*v)),
+            Self::Float(v) => Some(Number::Float(*v)),
+            _ => None,
+        };
+    }
+
+    /// Get a [`Value::Bool`] as [`bool`]
+    fn as_bool(&self) -> Option<bool> {
+        return match self {
+            Self::Bool(v) => Some(*v),
+            _ => None,
+        };
+    }
+}
+
+impl ValueSource for Value {
+    fn get_value(&self, name: &str) -> Option<Cow<'_, str>> {
+        return self.entry_or_self(name)?.as_scalar();
+    }
+
+    fn get_list(&self, name: &str) -> Option<Vec<&dyn ValueSource>> {
+        return self.entry(name)?.as_list();
+    }
+
+    fn get_child(&self, name: &str) -> Option<&dyn ValueSource> {
+        return self.entry(name)?.as_child();
+    }
+
+    fn get_number(&self, name: &str) -> Option<Number> {
+        return self.entry_or_self(name)?.as_number();
+    }
+
+    fn get_bool(&self, name: &str) -> Option<bool> {
+        return self.entry_or_self(name)?.as_bool();
+    }
+}
+
+/// A [`HashMap`] of typed values provides its entries like a [`Value::Map`], see [`Template::render_values`](crate::template::Template::render_values)
+impl<K, S> ValueSource for HashMap<K, Value, S>
+where
+    K: Borrow<str> + Eq + Hash,
+    S: BuildHasher,
+{
+    fn get_value(&self, name: &str) -> Option<Cow<'_, str>> {
+        return self.get(name)?.as_scalar();
+    }
+
+    fn get_list(&self, name: &str) -> Option<Vec<&dyn ValueSource>> {
+        return self.get(name)?.as_list();
+    }
+
+    fn get_child(&self, name: &str) -> Option<&dyn ValueSource> {
+        return self.get(name)?.as_child();
+    }
+
+    fn get_number(&self, name: &str) -> Option<Number> {
+        return self.get(name)?.as_number();
+    }
+
+    fn get_bool(&self, name: &str) -> Option<bool> {
+        return self.get(name)?.as_bool();
+    }
+}
+
+/// Typed positional values, see [`Template::render_positional_values`](crate::template::Template::render_positional_values)
+impl ValueSource for Positional<'_, Value> {
+    fn get_value(&self, name: &str) -> Option<Cow<'_, str>> {
+        return self.0.get(explicit_index(name)?)?.as_scalar();
+    }
+
+    fn get_list(&self, name: &str) -> Option<Vec<&dyn ValueSource>> {
+        return self.0.get(explicit_index(name)?)?.as_list();
+    }
+
+    fn get_child(&self, name: &str) -> Option<&dyn ValueSource> {
+        return self.0.get(explicit_index(name)?)?.as_child();
+    }
+
+    fn get_number(&self, name: &str) -> Option<Number> {
+        return self.0.get(explicit_index(name)?)?.as_number();
+    }
+
+    fn get_bool(&self, name: &str) -> Option<bool> {
+        return self.0.get(explicit_index(name)?)?.as_bool();
+    }
+
+    fn get_index(&self, index: usize) -> Option<Cow<'_, str>> {
+        return self.0.get(index)?.as_scalar();
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Value {
+    /// Serialize `value` into a [`Value`], only available with the `serde` feature
+    ///
+    /// Structs and maps become a [`Value::Map`], sequences and tuples become a [`Value::List`],
+    /// unit enum variants become a [`Value::String`] of their name and other enum variants become a [`Value::Map`] with their name as the only key.
+    /// # Errors
+    /// A [`TemplateErrorKind::Serialize`](crate::error::TemplateErrorKind::Serialize) error is returned if `value` could not be serialized,
+    /// like a map with non-string keys.
+    pub fn from_serialize<T: serde::Serialize + ?Sized>(value: &T) -> Result<Self, TemplateError> {
+        return crate::serialize::to_value(value)
+            .map_err(|err| return TemplateError::new(TemplateErrorKind::Serialize, err));
+    }
+}
+
+/// Implement [`From`] for integer types that always fit into a [`i64`]
+macro_rules! from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Value {
+                fn from(v: $t) -> Self {
+                    return Self::Integer(i64::from(v));
+                }
+            }
+        )*
+    };
+}
+
+from_integer!(i8, i16, i32, i64, u8, u16, u32);
+
+/// Convert a integer into a [`Value::Integer`], or a [`Value::String`] if it does not fit into a [`i64`]
+pub(crate) fn from_wide_integer<T: Copy + fmt::Display>(v: T) -> Value
+where
+    i64: TryFrom<T>,
+{
+    return match i64::try_from(v) {
+        Ok(v) => Value::Integer(v),
+        Err(_) => Value::String(v.to_string()),
+    };
+}
+
+/// Implement [`From`] for integer types that do not always fit into a [`i64`], larger values become a [`Value::String`]
+macro_rules! from_wide_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Value {
+                fn from(v: $t) -> Self {
+                    return from_wide_integer(v);
+                }
+            }
+        )*
+    };
+}
+
+from_wide_integer!(u64, usize, i128, u128, isize);
+
+impl From<f32> for Value {
+    fn from(v: f32) -> Self {
+        return Self::Float(f64::from(v));
+    }
+}
+
+impl From<f64> for Value {
+    fn from(v: f64) -> Self {
+        return Self::Float(v);
+    }
+}
+
+impl From<bool> for Value {
+    fn from(v: bool) -> Self {
+        return Self::Bool(v);
+    }
+}
+
+impl From<char> for Value {
+    fn from(v: char) -> Self {
+        return Self::String(v.to_string());
+    }
+}
+
+impl From<String> for Value {
+    fn from(v: String) -> Self {
+        return Self::String(v);
+    }
+}
+
+impl From<&str> for Value {
+    fn from(v: &str) -> Self {
+        return Self::String(v.to_string());
+    }
+}
+
+impl From<&String> for Value {
+    fn from(v: &String) -> Self {
+        return Self::String(v.clone());
+    }
+}
+
+impl From<Cow<'_, str>> for Value {
+    fn from(v: Cow<'_, str>) -> Self {
+        return Self::String(v.into_owned());
+    }
+}
+
+/// [`None`] becomes [`Value::Null`], which is treated as a missing value
+impl<V: Into<Value>> From<Option<V>> for Value {
+    fn from(v: Option<V>) -> Self {
+        return v.map_or(Self::Null, Into::into);
+    }
+}
+
+impl<V: Into<Value>> From<Vec<V>> for Value {
+    fn from(v: Vec<V>) -> Self {
+        return Self::List(v.into_iter().map(Into::into).collect());
+    }
+}
+
+impl From<BTreeMap<String, Value>> for Value {
+    fn from(v: BTreeMap<String, Value>) -> Self {
+        return Self::Map(v);
+    }
+}
+
+impl<K, V, S> From<HashMap<K, V, S>> for Value
+where
+    K: Into<String>,
+    V: Into<Value>,
+{
+    fn from(v: HashMap<K, V, S>) -> Self {
+        return Self::from_pairs(v);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_get_value() {
+        let value = Value::from_pairs(vec![("data1", Value::from("should")), ("list", Value::from(vec!["a"]))]);
+
+        assert_eq!(Some(Cow::Borrowed("should")), value.get_value("data1"));
+        assert_eq!(None, value.get_value("list"));
+        assert_eq!(None, value.get_value("data2"));
+        assert_eq!(None, value.get_value("."));
+        assert_eq!(Some(Cow::Borrowed("itself")), Value::from("itself").get_value("."));
+        assert_eq!(Some(Cow::Borrowed("false")), Value::Bool(false).get_value("."));
+        assert_eq!(Some(Cow::Borrowed("-10")), Value::Integer(-10).get_value("."));
+        assert_eq!(Some(Cow::Borrowed("1.5")), Value::Float(1.5).get_value("."));
+        assert_eq!(None, Value::Null.get_value("."));
+    }
+
+    #[test]
+    fn test_get_number() {
+        let value = Value::from_pairs(vec![
+            ("int", Value::Integer(-10)),
+            ("float", Value::Float(1.5)),
+            ("string", Value::from("10")),
+        ]);
+
+        assert_eq!(Some(Number::Integer(-10)), value.get_number("int"));
+        assert_eq!(Some(Number::Float(1.5)), value.get_number("float"));
+        assert_eq!(None, value.get_number("string"));
+        assert_eq!(None, value.get_number("missing"));
+        assert_eq!(Some(Number::Integer(3)), Value::Integer(3).get_number("."));
+    }
+
+    #[test]
+    fn test_get_bool() {
+        let value = Value::from_pairs(vec![("flag", Value::from(false)), ("string", Value::from("false"))]);
+
+        assert_eq!(Some(false), value.get_bool("flag"));
+        assert_eq!(None, value.get_bool("string"));
+        assert_eq!(Some(true), Value::from(true).get_bool("."));
+    }
+
+    #[test]
+    fn test_from() {
+        assert_eq!(Value::Integer(-3), Value::from(-3i8));
+        assert_eq!(Value::Integer(4_000_000_000), Value::from(4_000_000_000u32));
+        assert_eq!(Value::Integer(3), Value::from(vec![1, 2, 3].len()));
+        assert_eq!(Value::Integer(-7), Value::from(-7isize));
+        assert_eq!(Value::Integer(5), Value::from(5u64));
+        assert_eq!(Value::String(u64::MAX.to_string()), Value::from(u64::MAX));
+        assert_eq!(Value::Integer(-5), Value::from(-5i128));
+        assert_eq!(Value::String(i128::MIN.to_string()), Value::from(i128::MIN));
+        assert_eq!(Value::String(u128::MAX.to_string()), Value::from(u128::MAX));
+        assert_eq!(Value::Float(0.5), Value::from(0.5f32));
+        assert_eq!(Value::Bool(true), Value::from(true));
+        assert_eq!(Value::from("a"), Value::from('a'));
+        assert_eq!(Value::from("a"), Value::from(&"a".to_string()));
+        assert_eq!(Value::from("a"), Value::from(Cow::Borrowed("a")));
+        assert_eq!(Value::Null, Value::from(None::<i32>));
+        assert_eq!(Value::Integer(1), Value::from(Some(1)));
+
+        let mut map = HashMap::new();
+        map.insert("a", 1);
+        assert_eq!(Value::from_pairs(vec![("a", 1)]), Value::from(map));
+    }
+
+    #[test]
+    fn test_hashmap_source() {
+        let mut values = HashMap::new();
+        values.insert("int", Value::from(3));
+        values.insert("flag", Value::from(true));
+        values.insert("list", Value::from(vec!["a"]));
+        values.insert("map", Value::from_pairs(vec![("data1", "nested")]));
+
+        assert_eq!(Some(Cow::Borrowed("3")), values.get_value("int"));
+        assert_eq!(Some(Number::Integer(3)), values.get_number("int"));
+        assert_eq!(Some(true), values.get_bool("flag"));
+        assert_eq!(None, values.get_value("list"));
+        assert_eq!(1, values.get_list("list").map_or(0, |v| return v.len()));
+        let child = values.get_child("map").expect("Expected a child");
+        assert_eq!(Some(Cow::Borrowed("nested")), child.get_value("data1"));
+        assert!(values.get_child("int").is_none());
+    }
+
+    #[test]
+    fn test_get_list() {
+        let value = Value::from_pairs(vec![
+            ("data1", Value::from("should")),
+            ("list", Value::from(vec!["a", "b"])),
+        ]);
+
+        let list = value.get_list("list").expect("Expected a list");
+        let list: Vec<_> = list.iter().map(|v| return v.get_value(".")).collect();
+        assert_eq!(vec![Some(Cow::Borrowed("a")), Some(Cow::Borrowed("b"))], list);
+        assert!(value.get_list("data1").is_none());
+        assert!(value.get_list("data2").is_none());
+    }
+
+    #[test]
+    fn test_get_child() {
+        let value = Value::from_pairs(vec![
+            ("data1", Value::from("should")),
+            ("map", Value::from_pairs(vec![("data2", "nested")])),
+        ]);
+
+        let child = value.get_child("map").expect("Expected a child");
+        assert_eq!(Some(Cow::Borrowed("nested")), child.get_value("data2"));
+        assert!(value.get_child("data1").is_none());
+        assert!(value.get_child("data3").is_none());
+    }
+}